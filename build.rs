@@ -0,0 +1,24 @@
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+    #[cfg(feature = "ffi")]
+    generate_header();
+}
+
+// Generates the C header for the `ffi` module, which `tests/ffi.rs` checks against the copy in `include`
+#[cfg(feature = "ffi")]
+fn generate_header() {
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-changed=src/ffi.rs");
+    let crate_dir =
+        std::path::PathBuf::from(std::env::var("CARGO_MANIFEST_DIR").expect("Set by cargo"));
+    let out_dir = std::env::var("OUT_DIR").expect("Set by cargo");
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml"))
+        .expect("Unable to read cbindgen.toml");
+    // Only the `ffi` module is the C API, the rest of the crate's public constants are not
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(crate_dir.join("src").join("ffi.rs"))
+        .generate()
+        .expect("Unable to generate the C header")
+        .write_to_file(std::path::Path::new(&out_dir).join("dnd_dice_roller.h"));
+}
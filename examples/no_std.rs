@@ -0,0 +1,20 @@
+//! Parses and rolls dice without the standard library.
+//! `tests/no_std.rs` builds this with `cargo build --no-default-features --example no_std`.
+#![no_std]
+
+extern crate alloc;
+
+use alloc::vec::Vec;
+use core::str::FromStr;
+
+use dnd_dice_roller::{dice_result::DiceSetResults, error::DiceError, roll::Roll};
+use rand::SeedableRng;
+
+/// Parses a dice input string and rolls it with a seeded RNG.
+///
+/// # Errors
+/// Errors can occur if the dice input string is in the wrong format `DiceError::ParseError`.
+pub fn roll_seeded(input: &str, seed: u64) -> Result<Vec<DiceSetResults>, DiceError> {
+    let roll = Roll::from_str(input)?;
+    Ok(roll.roll_from_rng(rand_pcg::Pcg64Mcg::seed_from_u64(seed)))
+}
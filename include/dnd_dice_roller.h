@@ -0,0 +1,125 @@
+#ifndef DND_DICE_ROLLER_H
+#define DND_DICE_ROLLER_H
+
+/* Generated by cbindgen from src/ffi.rs with `cargo build --features ffi`, do not edit. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+// Represents the status returned by every C API function. Anything other than `Ok` has a message from `dice_last_error_message`.
+typedef enum DiceStatus {
+  // The call succeeded.
+  DICE_STATUS_OK = 0,
+  // A pointer passed in was null.
+  DICE_STATUS_NULL_POINTER = 1,
+  // The dice input string was not valid UTF-8.
+  DICE_STATUS_INVALID_UTF8 = 2,
+  // The dice input string is in the wrong format.
+  DICE_STATUS_PARSE_ERROR = 3,
+  // A dice set or dice index was past the end of the results.
+  DICE_STATUS_OUT_OF_RANGE = 4,
+  // Any other `DiceError`.
+  DICE_STATUS_ERROR = 5,
+} DiceStatus;
+
+// Represents the results of rolling a `DiceRoll`, one `DiceSetResults` per comma separated group, freed with `dice_results_free`.
+typedef struct DiceResults DiceResults;
+
+// Represents a parsed `Roll` owned by C code, freed with `dice_roll_free`.
+typedef struct DiceRoll DiceRoll;
+
+// The message for the last failed call on this thread, or null if no call has failed.
+// The message is owned by the library and is valid until the next failed call on this thread.
+const char *dice_last_error_message(void);
+
+// Parses a nul terminated dice input string e.g. `2d20 + 1 a, 3d6` into a new `DiceRoll` written to `roll`.
+//
+// # Safety
+// `input` must be null or a valid nul terminated string, and `roll` must be null or valid to write to.
+enum DiceStatus dice_roll_parse(const char *input,
+                                struct DiceRoll **roll);
+
+// Frees a `DiceRoll` created by `dice_roll_parse`. Does nothing when given null.
+//
+// # Safety
+// `roll` must be null or a `DiceRoll` from `dice_roll_parse` which has not been freed.
+void dice_roll_free(struct DiceRoll *roll);
+
+// Rolls a `DiceRoll` with a `Pcg64Mcg` RNG created from the seed, writing new `DiceResults` to `results`.
+//
+// # Safety
+// `roll` must be null or a valid `DiceRoll`, and `results` must be null or valid to write to.
+enum DiceStatus dice_roll_roll_seeded(const struct DiceRoll *roll,
+                                      uint64_t seed,
+                                      struct DiceResults **results);
+
+// Rolls a `DiceRoll` with the OS RNG, writing new `DiceResults` to `results`.
+//
+// # Safety
+// `roll` must be null or a valid `DiceRoll`, and `results` must be null or valid to write to.
+enum DiceStatus dice_roll_roll(const struct DiceRoll *roll, struct DiceResults **results);
+
+// Frees `DiceResults` created by rolling. Does nothing when given null.
+//
+// # Safety
+// `results` must be null or `DiceResults` from `dice_roll_roll` or `dice_roll_roll_seeded` which have not been freed.
+void dice_results_free(struct DiceResults *results);
+
+// Writes the number of dice sets, one per comma separated group, to `count`.
+//
+// # Safety
+// `results` must be null or valid `DiceResults`, and `count` must be null or valid to write to.
+enum DiceStatus dice_results_set_count(const struct DiceResults *results, size_t *count);
+
+// Writes the total of a dice set to `total`.
+//
+// # Safety
+// `results` must be null or valid `DiceResults`, and `total` must be null or valid to write to.
+enum DiceStatus dice_results_final_result(const struct DiceResults *results,
+                                          size_t set,
+                                          int32_t *total);
+
+// Writes the number of dice in a dice set, one per term e.g. two for `d20 + d4`, to `count`.
+//
+// # Safety
+// `results` must be null or valid `DiceResults`, and `count` must be null or valid to write to.
+enum DiceStatus dice_results_dice_count(const struct DiceResults *results,
+                                        size_t set,
+                                        size_t *count);
+
+// Writes the result of a single dice in a dice set, including its modifier, to `result`.
+//
+// # Safety
+// `results` must be null or valid `DiceResults`, and `result` must be null or valid to write to.
+enum DiceStatus dice_results_dice_result(const struct DiceResults *results,
+                                         size_t set,
+                                         size_t dice_index,
+                                         int32_t *result);
+
+// Copies up to `capacity` faces of the first roll of a dice into `faces`, and writes the number of faces rolled to `length`.
+// `faces` may be null to find the length alone.
+//
+// # Safety
+// `results` must be null or valid `DiceResults`, `faces` must be null or valid to write `capacity` faces to,
+// and `length` must be null or valid to write to.
+enum DiceStatus dice_results_first_roll(const struct DiceResults *results,
+                                        size_t set,
+                                        size_t dice_index,
+                                        uint32_t *faces,
+                                        size_t capacity,
+                                        size_t *length);
+
+// Copies up to `capacity` faces of the second roll of a dice with advantage or disadvantage into `faces`,
+// and writes the number of faces rolled to `length`. The length is zero for a dice without a second roll.
+//
+// # Safety
+// `results` must be null or valid `DiceResults`, `faces` must be null or valid to write `capacity` faces to,
+// and `length` must be null or valid to write to.
+enum DiceStatus dice_results_second_roll(const struct DiceResults *results,
+                                         size_t set,
+                                         size_t dice_index,
+                                         uint32_t *faces,
+                                         size_t capacity,
+                                         size_t *length);
+
+#endif  /* DND_DICE_ROLLER_H */
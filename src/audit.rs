@@ -0,0 +1,557 @@
+use crate::{dice::DiceKind, dice_result::DiceSetResults, error::DiceError, roll::Roll};
+
+use std::collections::BTreeMap;
+
+/// The significance level used by `DiceAudit::audit`. A test fails when its p-value is below it.
+pub const DEFAULT_SIGNIFICANCE: f64 = 0.01;
+
+/// The most sides a dice may have to be audited, as the chi-squared test counts every face.
+pub const MAX_SIDES: u32 = 1000;
+
+/// Streaks less likely than this are reported with a p-value of zero, rather than calculated exactly.
+const NEGLIGIBLE_PROBABILITY: f64 = 1e-18;
+
+/// Represents a record of individual dice outcomes grouped by the sides of the dice, in the order they were rolled.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct DiceAudit {
+    outcomes: BTreeMap<u32, Vec<u32>>,
+}
+
+impl DiceAudit {
+    /// Creates an empty `DiceAudit`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a single face rolled on a dice with the number of sides given.
+    ///
+    /// # Errors
+    /// Errors with `DiceError::InvalidFace` if the face cannot be rolled on the dice, or the dice has more than `MAX_SIDES` sides.
+    pub fn record(&mut self, sides: u32, face: u32) -> Result<(), DiceError> {
+        if face == 0 || face > sides || sides > MAX_SIDES {
+            return Err(DiceError::InvalidFace { face, sides });
+        }
+        self.outcomes.entry(sides).or_default().push(face);
+        Ok(())
+    }
+
+    /// Records every face from the results of a roll, taken from each `RollResult::first_roll` and `RollResult::second_roll`.
+    /// Exploding and rerolling dice are skipped, as their results are not uniform.
+    /// Fudge and custom dice are skipped too, as their faces are not the faces of a standard dice with the same sides,
+    /// as are dice with more than `MAX_SIDES` sides.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::str::FromStr;
+    /// use dnd_dice_roller::audit::DiceAudit;
+    /// use dnd_dice_roller::roll::Roll;
+    /// # use dnd_dice_roller::error::DiceError;
+    ///
+    /// let roll = Roll::from_str("2d20 + 1 a, 3d6")?;
+    /// let mut audit = DiceAudit::new();
+    /// audit.record_roll(&roll, &roll.roll())?;
+    /// assert_eq!(audit.outcomes(20).len(), 4);
+    /// assert_eq!(audit.outcomes(6).len(), 3);
+    /// # Ok::<(), DiceError>(())
+    /// ```
+    /// # Errors
+    /// Errors with `DiceError::InvalidFace` if a face cannot be rolled on its dice.
+    pub fn record_roll(
+        &mut self,
+        roll: &Roll,
+        results: &[DiceSetResults],
+    ) -> Result<(), DiceError> {
+        for (dice_set, dice_set_results) in roll.dice_sets().iter().zip(results) {
+            for (dice, result) in dice_set.dice().iter().zip(&dice_set_results.dice_results) {
+                if dice.kind != DiceKind::Standard
+                    || dice.explode
+                    || dice.reroll.is_some()
+                    || dice.sides > MAX_SIDES
+                {
+                    continue;
+                }
+                let second_roll = result.second_roll.iter().flatten();
+                for face in result.first_roll.iter().chain(second_roll) {
+                    self.record(dice.sides, *face)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// The faces recorded for dice with the number of sides given, in the order they were rolled.
+    #[must_use]
+    pub fn outcomes(&self, sides: u32) -> &[u32] {
+        self.outcomes.get(&sides).map_or(&[], Vec::as_slice)
+    }
+
+    /// Tests the faces recorded for each number of sides at the `DEFAULT_SIGNIFICANCE` level.
+    #[must_use]
+    pub fn audit(&self) -> AuditReport {
+        self.audit_with_significance(DEFAULT_SIGNIFICANCE)
+    }
+
+    /// Tests the faces recorded for each number of sides, failing any test with a p-value below the significance level.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::audit::DiceAudit;
+    /// # use dnd_dice_roller::error::DiceError;
+    ///
+    /// let mut audit = DiceAudit::new();
+    /// for _ in 0..60 {
+    ///     audit.record(6, 6)?;
+    /// }
+    /// let report = audit.audit_with_significance(0.05);
+    /// assert!(!report.passed());
+    /// assert!(report.dice[0].chi_squared.p_value < 0.05);
+    /// # Ok::<(), DiceError>(())
+    /// ```
+    #[must_use]
+    pub fn audit_with_significance(&self, significance: f64) -> AuditReport {
+        let dice = self
+            .outcomes
+            .iter()
+            .map(|(sides, faces)| SidesAudit::new(*sides, faces, significance))
+            .collect();
+        AuditReport { significance, dice }
+    }
+}
+
+/// Represents the result of a chi-squared goodness-of-fit test of the faces against a fair dice.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ChiSquaredTest {
+    /// The number of times each face was rolled, starting from 1.
+    pub counts: Vec<usize>,
+    /// The chi-squared statistic.
+    pub statistic: f64,
+    /// The degrees of freedom, one less than the sides.
+    pub degrees_of_freedom: u32,
+    /// The probability of a statistic at least this large from a fair dice.
+    pub p_value: f64,
+}
+
+/// Represents the result of a Wald-Wolfowitz runs test, where each face is classed as high or low.
+/// Too few runs suggests faces cluster together, and too many suggests they alternate.
+#[derive(Clone, PartialEq, Debug)]
+pub struct RunsTest {
+    /// The number of runs of high or low faces. The middle face of a dice with an odd number of sides is skipped.
+    pub runs: usize,
+    /// The expected number of runs.
+    pub expected_runs: f64,
+    /// The number of standard deviations the runs are from the expected runs.
+    pub z_score: f64,
+    /// The probability of runs at least this far from the expected runs, in either direction, from a fair dice.
+    pub p_value: f64,
+}
+
+/// Represents the longest streak of the same face rolled in a row.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Streak {
+    /// The face rolled.
+    pub face: u32,
+    /// The number of times it was rolled in a row.
+    pub length: usize,
+    /// The probability of a streak at least this long from a fair dice.
+    pub p_value: f64,
+}
+
+/// Represents the tests of the faces recorded for a single number of sides.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SidesAudit {
+    /// The sides of the dice.
+    pub sides: u32,
+    /// The number of faces recorded.
+    pub count: usize,
+    /// The chi-squared goodness-of-fit test.
+    pub chi_squared: ChiSquaredTest,
+    /// The runs test, if there are both high and low faces.
+    pub runs: Option<RunsTest>,
+    /// The longest streak, if any faces were recorded.
+    pub longest_streak: Option<Streak>,
+    /// Whether every test has a p-value at or above the significance level.
+    pub passed: bool,
+}
+
+impl SidesAudit {
+    fn new(sides: u32, faces: &[u32], significance: f64) -> Self {
+        let chi_squared = chi_squared_test(sides, faces);
+        let runs = runs_test(sides, faces);
+        let longest_streak = longest_streak(sides, faces);
+        let passed = chi_squared.p_value >= significance
+            && runs
+                .as_ref()
+                .is_none_or(|runs| runs.p_value >= significance)
+            && longest_streak
+                .as_ref()
+                .is_none_or(|streak| streak.p_value >= significance);
+
+        SidesAudit {
+            sides,
+            count: faces.len(),
+            chi_squared,
+            runs,
+            longest_streak,
+            passed,
+        }
+    }
+}
+
+/// Represents the result of auditing a `DiceAudit`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct AuditReport {
+    /// The significance level the tests were made at.
+    pub significance: f64,
+    /// The tests for each number of sides, from the fewest sides to the most.
+    pub dice: Vec<SidesAudit>,
+}
+
+impl AuditReport {
+    /// Whether the tests passed for every number of sides.
+    #[must_use]
+    pub fn passed(&self) -> bool {
+        self.dice.iter().all(|dice| dice.passed)
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn chi_squared_test(sides: u32, faces: &[u32]) -> ChiSquaredTest {
+    let mut counts = vec![0; sides as usize];
+    for face in faces {
+        counts[*face as usize - 1] += 1;
+    }
+    let expected = faces.len() as f64 / f64::from(sides);
+    let statistic = if expected > 0.0 {
+        counts
+            .iter()
+            .map(|count| (*count as f64 - expected).powi(2) / expected)
+            .sum()
+    } else {
+        0.0
+    };
+    let degrees_of_freedom = sides - 1;
+    let p_value = if degrees_of_freedom == 0 {
+        1.0
+    } else {
+        upper_regularized_gamma(f64::from(degrees_of_freedom) / 2.0, statistic / 2.0)
+    };
+
+    ChiSquaredTest {
+        counts,
+        statistic,
+        degrees_of_freedom,
+        p_value,
+    }
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn runs_test(sides: u32, faces: &[u32]) -> Option<RunsTest> {
+    // Twice the face is compared with sides + 1 to find the middle without rounding
+    let high_or_low: Vec<bool> = faces
+        .iter()
+        .filter(|face| **face * 2 != sides + 1)
+        .map(|face| *face * 2 > sides + 1)
+        .collect();
+    let high = high_or_low.iter().filter(|high| **high).count() as f64;
+    let low = high_or_low.len() as f64 - high;
+    let total = high + low;
+    if high == 0.0 || low == 0.0 || total < 3.0 {
+        return None;
+    }
+
+    let runs = 1 + high_or_low
+        .windows(2)
+        .filter(|pair| pair[0] != pair[1])
+        .count();
+    let expected_runs = 2.0 * high * low / total + 1.0;
+    let variance = 2.0 * high * low * (2.0 * high * low - total) / (total.powi(2) * (total - 1.0));
+    if variance <= 0.0 {
+        return None;
+    }
+    let z_score = (runs as f64 - expected_runs) / variance.sqrt();
+    let p_value = erfc(z_score.abs() / std::f64::consts::SQRT_2);
+
+    Some(RunsTest {
+        runs,
+        expected_runs,
+        z_score,
+        p_value,
+    })
+}
+
+fn longest_streak(sides: u32, faces: &[u32]) -> Option<Streak> {
+    let mut longest: Option<(u32, usize)> = None;
+    let mut current = 0;
+    for (index, face) in faces.iter().enumerate() {
+        current = if index > 0 && faces[index - 1] == *face {
+            current + 1
+        } else {
+            1
+        };
+        if longest.is_none_or(|(_, length)| current > length) {
+            longest = Some((*face, current));
+        }
+    }
+
+    longest.map(|(face, length)| Streak {
+        face,
+        length,
+        p_value: streak_p_value(sides, faces.len(), length),
+    })
+}
+
+/// The probability a fair dice rolled `rolls` times has a streak of at least `length` of the same face.
+#[allow(clippy::cast_precision_loss)]
+fn streak_p_value(sides: u32, rolls: usize, length: usize) -> f64 {
+    if length <= 1 {
+        return 1.0;
+    }
+    let repeat = 1.0 / f64::from(sides);
+    // A streak can start at any of the rolls, so this bounds the probability from above.
+    // Long streaks, e.g. from a dice which always rolls the same face, are far beyond any significance level.
+    let log_bound = (rolls as f64).ln() + (length - 1) as f64 * repeat.ln();
+    if log_bound < NEGLIGIBLE_PROBABILITY.ln() {
+        return 0.0;
+    }
+    // The probability of each current streak length, without ever having reached `length`
+    let mut streaks = vec![0.0; length];
+    streaks[1] = 1.0;
+    for _ in 1..rolls {
+        let total: f64 = streaks.iter().sum();
+        for streak in (2..length).rev() {
+            streaks[streak] = streaks[streak - 1] * repeat;
+        }
+        streaks[1] = total * (1.0 - repeat);
+    }
+    (1.0 - streaks.iter().sum::<f64>()).clamp(0.0, 1.0)
+}
+
+fn erfc(x: f64) -> f64 {
+    if x < 0.0 {
+        2.0 - erfc(-x)
+    } else {
+        upper_regularized_gamma(0.5, x * x)
+    }
+}
+
+/// The upper regularized gamma function Q(a, x), by series expansion below a + 1 and a continued fraction above it.
+fn upper_regularized_gamma(shape: f64, x: f64) -> f64 {
+    const ITERATIONS: usize = 1000;
+    const EPSILON: f64 = 1e-15;
+    const TINY: f64 = 1e-300;
+
+    if x <= 0.0 {
+        return 1.0;
+    }
+    let prefix = (-x + shape * x.ln() - ln_gamma(shape)).exp();
+
+    if x < shape + 1.0 {
+        let mut term = 1.0 / shape;
+        let mut sum = term;
+        let mut denominator = shape;
+        for _ in 0..ITERATIONS {
+            denominator += 1.0;
+            term *= x / denominator;
+            sum += term;
+            if term.abs() < sum.abs() * EPSILON {
+                break;
+            }
+        }
+        (1.0 - sum * prefix).clamp(0.0, 1.0)
+    } else {
+        // Modified Lentz's method
+        let mut partial_denominator = x + 1.0 - shape;
+        let mut lentz_c = 1.0 / TINY;
+        let mut lentz_d = 1.0 / partial_denominator;
+        let mut fraction = lentz_d;
+        for iteration in 1..=ITERATIONS {
+            #[allow(clippy::cast_precision_loss)]
+            let iteration = iteration as f64;
+            let partial_numerator = -iteration * (iteration - shape);
+            partial_denominator += 2.0;
+            lentz_d = partial_numerator * lentz_d + partial_denominator;
+            if lentz_d.abs() < TINY {
+                lentz_d = TINY;
+            }
+            lentz_c = partial_denominator + partial_numerator / lentz_c;
+            if lentz_c.abs() < TINY {
+                lentz_c = TINY;
+            }
+            lentz_d = 1.0 / lentz_d;
+            let delta = lentz_d * lentz_c;
+            fraction *= delta;
+            if (delta - 1.0).abs() < EPSILON {
+                break;
+            }
+        }
+        (prefix * fraction).clamp(0.0, 1.0)
+    }
+}
+
+/// The natural log of the gamma function, by the Lanczos approximation.
+fn ln_gamma(x: f64) -> f64 {
+    const COEFFICIENTS: [f64; 6] = [
+        76.180_091_729_471_46,
+        -86.505_320_329_416_77,
+        24.014_098_240_830_91,
+        -1.231_739_572_450_155,
+        0.001_208_650_973_866_179,
+        -0.000_005_395_239_384_953,
+    ];
+    let tmp = x + 5.5 - (x + 0.5) * (x + 5.5).ln();
+    let mut denominator = x;
+    let series = COEFFICIENTS
+        .iter()
+        .fold(1.000_000_000_190_015, |series, coefficient| {
+            denominator += 1.0;
+            series + coefficient / denominator
+        });
+    -tmp + (2.506_628_274_631_000_5 * series / x).ln()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use rand::{Rng, SeedableRng};
+    use std::str::FromStr;
+
+    const SEED: u64 = 42;
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-3,
+            "Expected {} to be close to {}",
+            actual,
+            expected
+        );
+    }
+
+    fn audit_of(sides: u32, faces: &[u32]) -> SidesAudit {
+        let mut audit = DiceAudit::new();
+        for face in faces {
+            audit.record(sides, *face).expect("Valid face");
+        }
+        audit.audit().dice.remove(0)
+    }
+
+    #[test]
+    fn chi_squared_p_values_match_tables() {
+        assert_close(upper_regularized_gamma(0.5, 3.841 / 2.0), 0.05);
+        assert_close(upper_regularized_gamma(2.5, 11.070 / 2.0), 0.05);
+        assert_close(upper_regularized_gamma(9.5, 36.191 / 2.0), 0.01);
+        assert_close(erfc(1.96 / std::f64::consts::SQRT_2), 0.05);
+    }
+
+    #[test]
+    fn streak_p_values_match_exact_probabilities() {
+        // Two coin flips match half of the time
+        assert_close(streak_p_value(2, 2, 2), 0.5);
+        // Three d6 rolls are all the same 1 in 36 times
+        assert_close(streak_p_value(6, 3, 3), 1.0 / 36.0);
+        assert_close(streak_p_value(6, 2, 3), 0.0);
+    }
+
+    #[test]
+    fn streak_p_values_of_long_streaks_are_negligible() {
+        assert_close(streak_p_value(6, 1_000_000, 1_000_000), 0.0);
+        assert_close(streak_p_value(2, 1_000_000, 100), 0.0);
+        assert!(streak_p_value(2, 1_000_000, 15) > 0.99);
+    }
+
+    #[test]
+    fn record_roll_skips_fudge_and_custom_dice() {
+        let roll = Roll::from_str("4dF + d{1,2,3} + d3").expect("Valid roll");
+        let results = roll.roll_from_rng(rand_pcg::Pcg64Mcg::seed_from_u64(SEED));
+        let mut audit = DiceAudit::new();
+        audit.record_roll(&roll, &results).expect("Valid faces");
+        assert_eq!(audit.outcomes(3).len(), 1);
+    }
+
+    #[test]
+    fn fair_rolls_pass() {
+        let mut rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let mut audit = DiceAudit::new();
+        for sides in [4, 6, 20] {
+            for _ in 0..6000 {
+                audit
+                    .record(sides, rng.gen_range(1..=sides))
+                    .expect("Valid face");
+            }
+        }
+        let report = audit.audit();
+        assert_eq!(report.dice.len(), 3);
+        assert!(report.passed(), "{:?}", report);
+    }
+
+    #[test]
+    fn loaded_dice_fail_chi_squared() {
+        let faces: Vec<u32> = (0..600)
+            .map(|index| [1, 2, 3, 4, 5, 6, 6][index % 7])
+            .collect();
+        let audit = audit_of(6, &faces);
+        assert!(audit.chi_squared.p_value < DEFAULT_SIGNIFICANCE);
+        assert!(!audit.passed);
+    }
+
+    #[test]
+    fn alternating_faces_fail_the_runs_test() {
+        let faces: Vec<u32> = (0..600)
+            .map(|index| [1, 6, 2, 5, 3, 4][index % 6])
+            .collect();
+        let audit = audit_of(6, &faces);
+        assert!(audit.chi_squared.p_value > 0.99);
+        let runs = audit.runs.expect("High and low faces");
+        assert_eq!(runs.runs, 600);
+        assert!(runs.p_value < DEFAULT_SIGNIFICANCE);
+        assert!(!audit.passed);
+    }
+
+    #[test]
+    fn long_streaks_fail() {
+        let mut rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let mut faces: Vec<u32> = (0..300).map(|_| rng.gen_range(1..=20)).collect();
+        faces.splice(100..100, std::iter::repeat_n(20, 6));
+        let audit = audit_of(20, &faces);
+        let streak = audit.longest_streak.expect("Faces recorded");
+        assert_eq!((streak.face, streak.length), (20, 6));
+        assert!(streak.p_value < DEFAULT_SIGNIFICANCE);
+        assert!(!audit.passed);
+    }
+
+    #[test]
+    fn records_rolls_by_sides() {
+        let roll = Roll::from_str("2d20 a, 3d6 + d4, 7k3").expect("Valid roll");
+        let results = roll.roll_from_rng(rand_pcg::Pcg64Mcg::seed_from_u64(SEED));
+        let mut audit = DiceAudit::new();
+        audit.record_roll(&roll, &results).expect("Valid faces");
+        assert_eq!(audit.outcomes(20).len(), 4);
+        assert_eq!(audit.outcomes(6).len(), 3);
+        assert_eq!(audit.outcomes(4).len(), 1);
+        assert!(audit.outcomes(10).is_empty());
+    }
+
+    #[test]
+    fn rejects_faces_the_dice_cannot_roll() {
+        let mut audit = DiceAudit::new();
+        assert!(audit.record(6, 7).is_err());
+        assert!(audit.record(6, 0).is_err());
+        assert!(audit.outcomes(6).is_empty());
+    }
+
+    #[test]
+    fn rejects_dice_with_too_many_sides() {
+        let mut audit = DiceAudit::new();
+        assert!(matches!(
+            audit.record(u32::MAX, 1),
+            Err(DiceError::InvalidFace {
+                face: 1,
+                sides: u32::MAX
+            })
+        ));
+        assert!(audit.record(MAX_SIDES, MAX_SIDES).is_ok());
+        assert!(audit.outcomes(u32::MAX).is_empty());
+        assert_eq!(audit.audit().dice.len(), 1);
+    }
+}
@@ -0,0 +1,227 @@
+use crate::{
+    dice::{Dice, Operation, RollType},
+    dice_result::RollResult,
+};
+
+use rand::Rng;
+
+/// The sides of each dice in an action roll.
+const SIDES: u32 = 6;
+
+/// Represents how dangerous an action is.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Position {
+    /// The character has a dominant advantage.
+    Controlled,
+    /// The character is acting under threat. The default position.
+    #[default]
+    Risky,
+    /// The character is overreaching or in serious trouble.
+    Desperate,
+}
+
+/// Represents how much an action can achieve.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Effect {
+    /// The action achieves nothing.
+    Zero,
+    /// The action achieves a weak or partial result.
+    Limited,
+    /// The action achieves what would be expected. The default effect.
+    #[default]
+    Standard,
+    /// The action achieves more than usual.
+    Great,
+    /// The action achieves an extraordinary result.
+    Extreme,
+}
+
+/// Represents the outcome of an action roll.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ActionOutcome {
+    /// Two or more 6s. The action succeeds with increased effect.
+    Critical,
+    /// A 6. The action succeeds.
+    FullSuccess,
+    /// A 4 or 5. The action succeeds with a consequence.
+    PartialSuccess,
+    /// A 1 to 3. The action fails and there is a consequence.
+    Failure,
+}
+
+/// Represents a Blades in the Dark action roll, a pool of d6s where the highest dice counts.
+/// A pool of zero dice rolls two and takes the lowest, and cannot critically succeed.
+#[derive(Clone, PartialEq, Debug)]
+pub struct ActionRoll {
+    /// The number of dice in the pool, usually the action rating plus any bonus dice.
+    pub dice_pool: u32,
+    /// How dangerous the action is.
+    pub position: Position,
+    /// How much the action can achieve.
+    pub effect: Effect,
+}
+
+impl ActionRoll {
+    /// Creates a new `ActionRoll`.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::blades::{ActionRoll, Effect, Position};
+    ///
+    /// let action_roll = ActionRoll::new(2, Position::Desperate, Effect::Great);
+    /// ```
+    #[must_use]
+    pub fn new(dice_pool: u32, position: Position, effect: Effect) -> Self {
+        ActionRoll {
+            dice_pool,
+            position,
+            effect,
+        }
+    }
+
+    /// The dice rolled for the pool, keeping the highest or the lowest of two for a pool of zero dice.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::blades::{ActionRoll, Effect, Position};
+    /// use dnd_dice_roller::dice::Keep;
+    ///
+    /// let dice = ActionRoll::new(0, Position::Risky, Effect::Standard).dice();
+    /// assert_eq!(dice.number_of_dice_to_roll, 2);
+    /// assert_eq!(dice.keep, Keep::Lowest(1));
+    /// ```
+    #[must_use]
+    pub fn dice(&self) -> Dice {
+        match self.dice_pool {
+            0 => Dice::new(2, SIDES, None, RollType::Regular, Operation::Addition).keep_lowest(1),
+            dice_pool => Dice::new(
+                dice_pool,
+                SIDES,
+                None,
+                RollType::Regular,
+                Operation::Addition,
+            )
+            .keep_highest(1),
+        }
+    }
+
+    /// Makes the action roll. Using underlying OS RNG for the dice roll.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::blades::{ActionRoll, Effect, Position};
+    ///
+    /// let result = ActionRoll::new(3, Position::Risky, Effect::Standard).roll_action();
+    /// assert!((1..=6).contains(&result.roll.result));
+    /// ```
+    #[must_use]
+    pub fn roll_action(&self) -> ActionRollResult {
+        let mut rng = rand::thread_rng();
+        self.roll_action_from_rng(&mut rng)
+    }
+
+    /// Makes the action roll. Uses a source of RNG passed in. Useful for testing.
+    ///
+    /// # Examples
+    /// ```
+    /// use rand::SeedableRng;
+    /// use dnd_dice_roller::blades::{ActionOutcome, ActionRoll, Effect, Position};
+    ///
+    /// let rng = rand_pcg::Pcg64Mcg::seed_from_u64(42);
+    /// let result = ActionRoll::new(3, Position::Risky, Effect::Standard).roll_action_from_rng(rng);
+    /// assert_eq!(result.roll.first_roll, vec![2, 6, 5]);
+    /// assert_eq!(result.outcome, ActionOutcome::FullSuccess);
+    /// ```
+    pub fn roll_action_from_rng<R: Rng + Sized>(&self, rng: R) -> ActionRollResult {
+        let roll = self.dice().roll_dice_from_rng(rng);
+        let sixes = roll
+            .first_roll
+            .iter()
+            .filter(|face| **face == SIDES)
+            .count();
+        let outcome = match roll.result {
+            6 if sixes > 1 && self.dice_pool > 0 => ActionOutcome::Critical,
+            6 => ActionOutcome::FullSuccess,
+            4 | 5 => ActionOutcome::PartialSuccess,
+            _ => ActionOutcome::Failure,
+        };
+
+        ActionRollResult {
+            roll,
+            outcome,
+            position: self.position,
+            effect: self.effect,
+        }
+    }
+}
+
+/// Represents the result of an `ActionRoll`.
+#[derive(PartialEq, Debug)]
+pub struct ActionRollResult {
+    /// The roll of the dice pool.
+    pub roll: RollResult,
+    /// The outcome of the roll.
+    pub outcome: ActionOutcome,
+    /// The position the action was rolled in.
+    pub position: Position,
+    /// The effect the action was rolled with.
+    pub effect: Effect,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use rand::SeedableRng;
+
+    const SEED: u64 = 42;
+
+    fn outcome(dice_pool: u32, rng: &mut rand_pcg::Pcg64Mcg) -> ActionRollResult {
+        ActionRoll::new(dice_pool, Position::default(), Effect::default()).roll_action_from_rng(rng)
+    }
+
+    #[test]
+    fn produces_predictable_results() {
+        let mut rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let result = outcome(1, &mut rng);
+        assert_eq!(result.roll.result, 2);
+        assert_eq!(result.outcome, ActionOutcome::Failure);
+        assert_eq!(result.position, Position::Risky);
+        assert_eq!(result.effect, Effect::Standard);
+    }
+
+    #[test]
+    fn zero_dice_takes_the_lowest_of_two() {
+        let mut rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let result = outcome(0, &mut rng);
+        assert_eq!(result.roll.first_roll, vec![2, 6]);
+        assert_eq!(result.roll.result, 2);
+    }
+
+    #[test]
+    fn result_is_the_highest_dice() {
+        let mut rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        for dice_pool in 1..6 {
+            let result = outcome(dice_pool, &mut rng);
+            let highest = result.roll.first_roll.iter().max().copied();
+            #[allow(clippy::cast_sign_loss)]
+            let total = result.roll.result as u32;
+            assert_eq!(Some(total), highest);
+        }
+    }
+
+    #[test]
+    fn multiple_sixes_are_a_critical() {
+        let mut rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let critical = (0..200).any(|_| outcome(4, &mut rng).outcome == ActionOutcome::Critical);
+        assert!(critical);
+    }
+
+    #[test]
+    fn zero_dice_never_critical() {
+        let mut rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        for _ in 0..500 {
+            assert_ne!(outcome(0, &mut rng).outcome, ActionOutcome::Critical);
+        }
+    }
+}
@@ -0,0 +1,319 @@
+use crate::{
+    dice::{Dice, Operation, RollType},
+    dice_result::RollResult,
+};
+
+use rand::Rng;
+
+/// The number of successes or failures needed to stabilise or die.
+const SAVES_NEEDED: u32 = 3;
+/// The lowest total which counts as a success.
+const SUCCESS_THRESHOLD: i32 = 10;
+
+/// Represents how a single death saving throw turned out.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DeathSaveOutcome {
+    /// A natural 20. The creature regains 1 hit point.
+    CriticalSuccess,
+    /// A total of 10 or higher. Counts as one success.
+    Success,
+    /// A total below 10. Counts as one failure.
+    Failure,
+    /// A natural 1. Counts as two failures.
+    CriticalFailure,
+}
+
+/// Represents the condition of a creature making death saving throws.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum DeathSaveStatus {
+    /// The creature is at 0 hit points and must keep rolling.
+    Dying,
+    /// The creature has three successes and no longer rolls.
+    Stable,
+    /// The creature rolled a natural 20 and is back on 1 hit point.
+    Revived,
+    /// The creature has three failures.
+    Dead,
+}
+
+/// Represents the result of a single death saving throw.
+#[derive(PartialEq, Debug)]
+pub struct DeathSaveResult {
+    /// The roll of the d20, including any modifier.
+    pub roll: RollResult,
+    /// How the saving throw turned out.
+    pub outcome: DeathSaveOutcome,
+    /// The creature's condition after the saving throw.
+    pub status: DeathSaveStatus,
+}
+
+/// Tracks the death saving throws of a creature at 0 hit points.
+#[derive(Debug)]
+pub struct DeathSaves {
+    dice: Dice,
+    successes: u32,
+    failures: u32,
+    status: DeathSaveStatus,
+}
+
+impl Default for DeathSaves {
+    fn default() -> Self {
+        Self::new(None, RollType::Regular)
+    }
+}
+
+impl DeathSaves {
+    /// Creates a new `DeathSaves` for a dying creature with no successes or failures.
+    /// The modifier and roll type apply to every saving throw e.g. from a Bless spell.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::death_saves::DeathSaves;
+    /// use dnd_dice_roller::dice::RollType;
+    ///
+    /// let death_saves = DeathSaves::new(Some(2), RollType::Regular);
+    /// ```
+    #[must_use]
+    pub fn new(modifier: Option<i32>, roll_type: RollType) -> Self {
+        DeathSaves {
+            dice: Dice::new(1, 20, modifier, roll_type, Operation::Addition),
+            successes: 0,
+            failures: 0,
+            status: DeathSaveStatus::Dying,
+        }
+    }
+
+    /// The number of successes so far.
+    #[must_use]
+    pub fn successes(&self) -> u32 {
+        self.successes
+    }
+
+    /// The number of failures so far.
+    #[must_use]
+    pub fn failures(&self) -> u32 {
+        self.failures
+    }
+
+    /// The creature's current condition.
+    #[must_use]
+    pub fn status(&self) -> DeathSaveStatus {
+        self.status
+    }
+
+    /// Rolls a death saving throw and records its outcome. Using underlying OS RNG for the dice roll.
+    /// Returns `None` when the creature is no longer dying.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::death_saves::DeathSaves;
+    ///
+    /// let mut death_saves = DeathSaves::default();
+    /// let result = death_saves.roll_death_save();
+    /// assert!(result.is_some());
+    /// ```
+    pub fn roll_death_save(&mut self) -> Option<DeathSaveResult> {
+        let mut rng = rand::thread_rng();
+        self.roll_death_save_from_rng(&mut rng)
+    }
+
+    /// Rolls a death saving throw and records its outcome. Uses a source of RNG passed in. Useful for testing.
+    /// Returns `None` when the creature is no longer dying.
+    ///
+    /// # Examples
+    /// ```
+    /// use rand::SeedableRng;
+    /// use dnd_dice_roller::death_saves::{DeathSaves, DeathSaveOutcome};
+    ///
+    /// let rng = rand_pcg::Pcg64Mcg::seed_from_u64(42);
+    /// let mut death_saves = DeathSaves::default();
+    /// let result = death_saves.roll_death_save_from_rng(rng).unwrap();
+    /// assert_eq!(result.outcome, DeathSaveOutcome::Failure);
+    /// assert_eq!(death_saves.failures(), 1);
+    /// ```
+    pub fn roll_death_save_from_rng<R: Rng + Sized>(&mut self, rng: R) -> Option<DeathSaveResult> {
+        if self.status != DeathSaveStatus::Dying {
+            return None;
+        }
+
+        let roll = self.dice.roll_dice_from_rng(rng);
+        let natural = roll.result - self.dice.modifier.unwrap_or(0);
+        let outcome = match natural {
+            20 => DeathSaveOutcome::CriticalSuccess,
+            1 => DeathSaveOutcome::CriticalFailure,
+            _ if roll.result >= SUCCESS_THRESHOLD => DeathSaveOutcome::Success,
+            _ => DeathSaveOutcome::Failure,
+        };
+        self.record(outcome);
+
+        Some(DeathSaveResult {
+            roll,
+            outcome,
+            status: self.status,
+        })
+    }
+
+    /// Records damage taken while at 0 hit points, which counts as one failure or two from a critical hit.
+    /// A revived creature is at 1 hit point, so damage drops it to 0 and it starts making death saves afresh without a failure.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::death_saves::{DeathSaves, DeathSaveStatus};
+    ///
+    /// let mut death_saves = DeathSaves::default();
+    /// death_saves.take_damage(true);
+    /// death_saves.take_damage(false);
+    /// assert_eq!(death_saves.status(), DeathSaveStatus::Dead);
+    /// ```
+    pub fn take_damage(&mut self, critical_hit: bool) {
+        if self.status == DeathSaveStatus::Dead {
+            return;
+        }
+        match self.status {
+            DeathSaveStatus::Revived => {
+                self.reset();
+                return;
+            }
+            // A stable creature is still at 0 hit points, so it starts dying again with the failure
+            DeathSaveStatus::Stable => self.reset(),
+            _ => {}
+        }
+        self.record(if critical_hit {
+            DeathSaveOutcome::CriticalFailure
+        } else {
+            DeathSaveOutcome::Failure
+        });
+    }
+
+    /// Clears all successes and failures, e.g. after the creature regains hit points.
+    pub fn reset(&mut self) {
+        self.successes = 0;
+        self.failures = 0;
+        self.status = DeathSaveStatus::Dying;
+    }
+
+    fn record(&mut self, outcome: DeathSaveOutcome) {
+        match outcome {
+            DeathSaveOutcome::CriticalSuccess => {
+                self.successes = 0;
+                self.failures = 0;
+                self.status = DeathSaveStatus::Revived;
+            }
+            DeathSaveOutcome::Success => self.successes += 1,
+            DeathSaveOutcome::Failure => self.failures += 1,
+            DeathSaveOutcome::CriticalFailure => self.failures += 2,
+        }
+
+        if self.failures >= SAVES_NEEDED {
+            self.failures = SAVES_NEEDED;
+            self.status = DeathSaveStatus::Dead;
+        } else if self.successes >= SAVES_NEEDED {
+            self.status = DeathSaveStatus::Stable;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use rand::SeedableRng;
+
+    const SEED: u64 = 42;
+
+    #[test]
+    fn produces_predictable_death_save() {
+        let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let mut death_saves = DeathSaves::default();
+        let result = death_saves.roll_death_save_from_rng(rng).unwrap();
+        assert_eq!(result.roll, RollResult::new(vec![6], None, 6));
+        assert_eq!(result.outcome, DeathSaveOutcome::Failure);
+        assert_eq!(result.status, DeathSaveStatus::Dying);
+    }
+
+    #[test]
+    fn modifier_counts_towards_success() {
+        let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let mut death_saves = DeathSaves::new(Some(4), RollType::Regular);
+        let result = death_saves.roll_death_save_from_rng(rng).unwrap();
+        assert_eq!(result.roll.result, 10);
+        assert_eq!(result.outcome, DeathSaveOutcome::Success);
+        assert_eq!(death_saves.successes(), 1);
+    }
+
+    #[test]
+    fn three_successes_stabilise() {
+        let mut death_saves = DeathSaves::default();
+        for _ in 0..3 {
+            death_saves.record(DeathSaveOutcome::Success);
+        }
+        assert_eq!(death_saves.status(), DeathSaveStatus::Stable);
+        assert!(death_saves.roll_death_save().is_none());
+    }
+
+    #[test]
+    fn critical_failure_counts_twice() {
+        let mut death_saves = DeathSaves::default();
+        death_saves.record(DeathSaveOutcome::CriticalFailure);
+        assert_eq!(death_saves.failures(), 2);
+        assert_eq!(death_saves.status(), DeathSaveStatus::Dying);
+        death_saves.record(DeathSaveOutcome::Failure);
+        assert_eq!(death_saves.status(), DeathSaveStatus::Dead);
+    }
+
+    #[test]
+    fn critical_success_revives() {
+        let mut death_saves = DeathSaves::default();
+        death_saves.record(DeathSaveOutcome::Failure);
+        death_saves.record(DeathSaveOutcome::Failure);
+        death_saves.record(DeathSaveOutcome::CriticalSuccess);
+        assert_eq!(death_saves.status(), DeathSaveStatus::Revived);
+        assert_eq!(death_saves.failures(), 0);
+    }
+
+    #[test]
+    fn damage_while_stable_resumes_dying() {
+        let mut death_saves = DeathSaves::default();
+        for _ in 0..3 {
+            death_saves.record(DeathSaveOutcome::Success);
+        }
+        death_saves.take_damage(false);
+        assert_eq!(death_saves.status(), DeathSaveStatus::Dying);
+        assert_eq!(death_saves.successes(), 0);
+        assert_eq!(death_saves.failures(), 1);
+    }
+
+    #[test]
+    fn critical_hit_while_stable_counts_two_failures() {
+        let mut death_saves = DeathSaves::default();
+        for _ in 0..3 {
+            death_saves.record(DeathSaveOutcome::Success);
+        }
+        death_saves.take_damage(true);
+        assert_eq!(death_saves.status(), DeathSaveStatus::Dying);
+        assert_eq!(death_saves.failures(), 2);
+    }
+
+    #[test]
+    fn damage_after_reviving_resumes_dying() {
+        let mut death_saves = DeathSaves::default();
+        death_saves.record(DeathSaveOutcome::CriticalSuccess);
+        death_saves.take_damage(true);
+        assert_eq!(death_saves.status(), DeathSaveStatus::Dying);
+        assert_eq!(death_saves.successes(), 0);
+        assert_eq!(death_saves.failures(), 0);
+        assert!(death_saves.roll_death_save().is_some());
+    }
+
+    #[test]
+    fn rolls_until_resolved() {
+        let mut rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let mut death_saves = DeathSaves::default();
+        let mut rolls = 0;
+        while death_saves.roll_death_save_from_rng(&mut rng).is_some() {
+            rolls += 1;
+        }
+        assert!((1..=5).contains(&rolls));
+        assert_ne!(death_saves.status(), DeathSaveStatus::Dying);
+    }
+}
@@ -1,15 +1,22 @@
-use crate::dice_result::RollResult;
+use crate::{dice_result::RollResult, distribution::Distribution, error::DiceError};
 
-use dice_command_parser::{
-    dice_roll::Operation as CommandOperation, dice_roll::RollType as CommandRollType,
-    dice_roll_with_op::DiceRollWithOp,
-};
+use crate::die_roller::DieRoller;
 use rand::Rng;
 
-use std::cmp::{max, min};
+use alloc::{
+    collections::BTreeMap,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::cmp::{max, min};
+use core::fmt;
+
+/// The most dice which can be rolled or kept in a roll and keep roll.
+const ROLL_AND_KEEP_CAP: u32 = 10;
 
 /// Represents a set of homogenous dice. E.G. Three d6
-#[derive(PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug)]
 pub struct Dice {
     /// The number of dice in the set of homegenous dice.
     pub number_of_dice_to_roll: u32,
@@ -21,10 +28,156 @@ pub struct Dice {
     pub roll_type: RollType,
     /// Whether this dice should be added or taken from the overall total
     pub operation: Operation,
+    /// What the faces of each dice show.
+    pub kind: DiceKind,
+    /// Whether a dice which rolls its highest face is rolled again and added, for as long as it keeps rolling its highest face.
+    /// Only `DiceKind::Standard` dice explode.
+    pub explode: bool,
+    /// Which of the dice count towards the total.
+    pub keep: Keep,
+    /// Dice which first roll this face or lower are rolled once more, keeping the second roll. e.g. `Some(1)` rerolls 1s.
+    pub reroll: Option<u32>,
+}
+
+/// Represents which dice in a set count towards the total.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Keep {
+    /// Every dice is added together.
+    All,
+    /// Only the given number of highest dice are added together.
+    Highest(u32),
+    /// Only the given number of lowest dice are added together.
+    Lowest(u32),
+}
+
+/// Represents what the faces of a dice show.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DiceKind {
+    /// The faces are numbered from 1 to the number of sides.
+    Standard,
+    /// A Fate/Fudge dice with three sides showing -1, 0 and +1. Displayed as `-`, blank and `+`.
+    Fudge,
+    /// A dice with the faces given, in order. e.g. `d{2,4,6,8}` or `d{skull,blank,blank}`
+    Custom(Vec<Face>),
+}
+
+impl DiceKind {
+    /// The value of the face rolled, where `face` is the 1-based position of the face on the dice.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::dice::{DiceKind, Face};
+    ///
+    /// assert_eq!(DiceKind::Standard.face_value(4), 4);
+    /// assert_eq!(DiceKind::Fudge.face_value(1), -1);
+    /// assert_eq!(DiceKind::Custom(vec![Face::Number(2), Face::Number(4)]).face_value(2), 4);
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn face_value(&self, face: u32) -> i32 {
+        match self {
+            DiceKind::Standard => face as i32,
+            DiceKind::Fudge => face as i32 - 2,
+            DiceKind::Custom(faces) => faces.get(face as usize - 1).map_or(0, Face::value),
+        }
+    }
+
+    /// The face rolled, where `face` is the 1-based position of the face on the dice.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::dice::{DiceKind, Face};
+    ///
+    /// assert_eq!(DiceKind::Standard.face(4), Face::Number(4));
+    /// let kind = DiceKind::Custom(vec![Face::Symbol("skull".to_string()), Face::Number(1)]);
+    /// assert_eq!(kind.face(1), Face::Symbol("skull".to_string()));
+    /// ```
+    #[must_use]
+    pub fn face(&self, face: u32) -> Face {
+        match self {
+            DiceKind::Custom(faces) => faces
+                .get(face as usize - 1)
+                .cloned()
+                .unwrap_or(Face::Number(0)),
+            _ => Face::Number(self.face_value(face)),
+        }
+    }
+}
+
+/// Represents what a single face of a custom dice shows.
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Face {
+    /// A number, which counts towards the total.
+    Number(i32),
+    /// A symbol e.g. `skull`, which does not count towards the total.
+    Symbol(String),
+}
+
+impl Face {
+    /// How much the face counts towards the total.
+    #[must_use]
+    pub fn value(&self) -> i32 {
+        match self {
+            Face::Number(value) => *value,
+            Face::Symbol(_) => 0,
+        }
+    }
+}
+
+impl fmt::Display for Face {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Face::Number(value) => write!(f, "{value}"),
+            Face::Symbol(symbol) => write!(f, "{symbol}"),
+        }
+    }
+}
+
+/// Represents custom dice faces which can be referred to by name in dice input strings. e.g. `2d[averaging]`
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct NamedDice {
+    faces: BTreeMap<String, Vec<Face>>,
+}
+
+impl NamedDice {
+    /// Creates an empty collection of named dice.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a named dice, replacing any existing dice with the same name.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::dice::{Face, NamedDice};
+    ///
+    /// let mut named_dice = NamedDice::new();
+    /// let faces = [2, 3, 3, 4, 4, 5].iter().map(|value| Face::Number(*value)).collect();
+    /// named_dice.insert("averaging", faces)?;
+    /// # Ok::<(), dnd_dice_roller::error::DiceError>(())
+    /// ```
+    /// # Errors
+    /// Errors with `DiceError::NoFaces` if no faces are given, as the dice could not be rolled.
+    pub fn insert(&mut self, name: &str, faces: Vec<Face>) -> Result<(), DiceError> {
+        if faces.is_empty() {
+            return Err(DiceError::NoFaces);
+        }
+        self.faces.insert(name.to_string(), faces);
+        Ok(())
+    }
+
+    /// Gets the faces of a named dice.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&[Face]> {
+        self.faces.get(name).map(Vec::as_slice)
+    }
 }
 
 /// Represents the advantage or disadvantage on a roll.
-#[derive(PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum RollType {
     /// The roll occurs twice, with the highest result being taken.
     Advantage,
@@ -35,7 +188,7 @@ pub enum RollType {
 }
 
 /// Represents whether the dice result should be added or taken away from the total.
-#[derive(PartialEq, Debug)]
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum Operation {
     /// The roll should be taken away from the overall total
     Addition,
@@ -44,27 +197,6 @@ pub enum Operation {
 }
 
 impl Dice {
-    pub(crate) fn from_parsed_dice_roll(parsed_roll: &DiceRollWithOp) -> Self {
-        let roll_type = match parsed_roll.dice_roll.roll_type {
-            CommandRollType::Regular => RollType::Regular,
-            CommandRollType::WithAdvantage => RollType::Advantage,
-            CommandRollType::WithDisadvantage => RollType::Disadvantage,
-        };
-
-        let operation = match parsed_roll.operation {
-            CommandOperation::Addition => Operation::Addition,
-            CommandOperation::Subtraction => Operation::Subtraction,
-        };
-
-        Dice {
-            number_of_dice_to_roll: parsed_roll.dice_roll.number_of_dice_to_roll,
-            sides: parsed_roll.dice_roll.dice_sides,
-            modifier: parsed_roll.dice_roll.modifier,
-            roll_type,
-            operation,
-        }
-    }
-
     /// Constructs a new dice
     /// # Examples
     /// ```
@@ -86,7 +218,168 @@ impl Dice {
             modifier,
             roll_type,
             operation,
+            kind: DiceKind::Standard,
+            explode: false,
+            keep: Keep::All,
+            reroll: None,
+        }
+    }
+
+    /// Constructs a new set of Fate/Fudge dice, which each roll -1, 0 or +1.
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::dice::{Dice, RollType, Operation};
+    /// // 4dF+2
+    /// let dice = Dice::fudge(4, Some(2), RollType::Regular, Operation::Addition);
+    /// ```
+    #[must_use]
+    pub fn fudge(
+        number_of_dice: u32,
+        modifier: Option<i32>,
+        roll_type: RollType,
+        operation: Operation,
+    ) -> Self {
+        Dice {
+            number_of_dice_to_roll: number_of_dice,
+            sides: 3,
+            modifier,
+            roll_type,
+            operation,
+            kind: DiceKind::Fudge,
+            explode: false,
+            keep: Keep::All,
+            reroll: None,
+        }
+    }
+
+    /// Constructs a new set of dice with the faces given, which each roll one of the faces with equal probability.
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::dice::{Dice, Face, RollType, Operation};
+    /// // 2d{2,4,6,8}
+    /// let faces = vec![Face::Number(2), Face::Number(4), Face::Number(6), Face::Number(8)];
+    /// let dice = Dice::custom(2, faces, None, RollType::Regular, Operation::Addition)?;
+    /// assert_eq!(dice.sides, 4);
+    /// # Ok::<(), dnd_dice_roller::error::DiceError>(())
+    /// ```
+    /// # Errors
+    /// Errors with `DiceError::NoFaces` if no faces are given, as the dice could not be rolled.
+    #[allow(clippy::cast_possible_truncation)]
+    pub fn custom(
+        number_of_dice: u32,
+        faces: Vec<Face>,
+        modifier: Option<i32>,
+        roll_type: RollType,
+        operation: Operation,
+    ) -> Result<Self, DiceError> {
+        if faces.is_empty() {
+            return Err(DiceError::NoFaces);
+        }
+        Ok(Dice {
+            number_of_dice_to_roll: number_of_dice,
+            sides: faces.len() as u32,
+            modifier,
+            roll_type,
+            operation,
+            kind: DiceKind::Custom(faces),
+            explode: false,
+            keep: Keep::All,
+            reroll: None,
+        })
+    }
+
+    /// Constructs a Legend of the Five Rings roll and keep `XkY` roll of exploding d10s, keeping the highest.
+    /// Rolled dice over ten become one kept dice for every two, and kept dice over ten each add 2 to the modifier.
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::dice::{Dice, Keep, RollType, Operation};
+    /// // 12k9 becomes 10k10, 13k11 becomes 10k10+4
+    /// let dice = Dice::roll_and_keep(12, 9, None, RollType::Regular, Operation::Addition);
+    /// assert_eq!((dice.number_of_dice_to_roll, dice.keep), (10, Keep::Highest(10)));
+    ///
+    /// let dice = Dice::roll_and_keep(13, 11, None, RollType::Regular, Operation::Addition);
+    /// assert_eq!((dice.number_of_dice_to_roll, dice.keep, dice.modifier), (10, Keep::Highest(10), Some(4)));
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn roll_and_keep(
+        rolled: u32,
+        kept: u32,
+        modifier: Option<i32>,
+        roll_type: RollType,
+        operation: Operation,
+    ) -> Self {
+        let mut kept = kept.min(rolled);
+        let mut rolled = rolled;
+        if rolled > ROLL_AND_KEEP_CAP {
+            kept += (rolled - ROLL_AND_KEEP_CAP) / 2;
+            rolled = ROLL_AND_KEEP_CAP;
+        }
+        let mut modifier = modifier;
+        if kept > ROLL_AND_KEEP_CAP {
+            let bonus = (kept - ROLL_AND_KEEP_CAP) as i32 * 2;
+            modifier = Some(modifier.unwrap_or(0) + bonus);
+            kept = ROLL_AND_KEEP_CAP;
         }
+        Dice::new(rolled, 10, modifier, roll_type, operation)
+            .exploding()
+            .keep_highest(kept)
+    }
+
+    /// Keeps only the given number of highest dice, so the rest do not count towards the total.
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::dice::{Dice, Keep, RollType, Operation};
+    /// // The highest three of 4d6
+    /// let dice = Dice::new(4, 6, None, RollType::Regular, Operation::Addition).keep_highest(3);
+    /// assert_eq!(dice.keep, Keep::Highest(3));
+    /// ```
+    #[must_use]
+    pub fn keep_highest(mut self, keep: u32) -> Self {
+        self.keep = Keep::Highest(keep);
+        self
+    }
+
+    /// Keeps only the given number of lowest dice, so the rest do not count towards the total.
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::dice::{Dice, Keep, RollType, Operation};
+    /// // The lowest of 2d6
+    /// let dice = Dice::new(2, 6, None, RollType::Regular, Operation::Addition).keep_lowest(1);
+    /// assert_eq!(dice.keep, Keep::Lowest(1));
+    /// ```
+    #[must_use]
+    pub fn keep_lowest(mut self, keep: u32) -> Self {
+        self.keep = Keep::Lowest(keep);
+        self
+    }
+
+    /// Rerolls dice which first roll `face` or lower once, keeping the second roll even if it is also `face` or lower.
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::dice::{Dice, RollType, Operation};
+    /// // A d8 which rerolls 1s and 2s, e.g. from Great Weapon Fighting
+    /// let dice = Dice::new(1, 8, None, RollType::Regular, Operation::Addition).rerolling(2);
+    /// assert_eq!(dice.reroll, Some(2));
+    /// ```
+    #[must_use]
+    pub fn rerolling(mut self, face: u32) -> Self {
+        self.reroll = Some(face);
+        self
+    }
+
+    /// Makes the dice explode, so a dice which rolls its highest face is rolled again and added.
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::dice::{Dice, RollType, Operation};
+    /// // An exploding d6
+    /// let dice = Dice::new(1, 6, None, RollType::Regular, Operation::Addition).exploding();
+    /// assert!(dice.explode);
+    /// ```
+    #[must_use]
+    pub fn exploding(mut self) -> Self {
+        self.explode = true;
+        self
     }
 
     /// Rolls a dice and produces a `RollResult`. Using underlying OS RNG for the dice roll.
@@ -100,6 +393,7 @@ impl Dice {
     /// let result = dice.roll_dice();
     /// # Ok::<(), DiceError>(())
     /// ```
+    #[cfg(feature = "std")]
     #[must_use]
     pub fn roll_dice(&self) -> RollResult {
         let mut rng = rand::thread_rng();
@@ -118,19 +412,41 @@ impl Dice {
     /// let result = dice.roll_dice_from_rng(rng);
     /// assert_eq!(result.result, 2);
     /// ```
-    #[allow(clippy::cast_possible_wrap)]
+    ///
+    /// # Panics
+    /// Will not panic, advantage and disadvantage rolls always produce a second set of results.
     pub fn roll_dice_from_rng<R: Rng + Sized>(&self, mut rng: R) -> RollResult {
+        self.roll_dice_with(&mut rng)
+    }
+
+    /// Rolls a dice and produces a `RollResult`. Uses a `DieRoller` passed in, which can be chosen at runtime.
+    ///
+    /// # Examples
+    /// ```
+    /// use rand::SeedableRng;
+    /// use dnd_dice_roller::dice::{Dice, RollType, Operation};
+    /// use dnd_dice_roller::die_roller::DieRoller;
+    ///
+    /// let mut roller: Box<dyn DieRoller> = Box::new(rand_pcg::Pcg64Mcg::seed_from_u64(42));
+    /// let dice = Dice::new(1, 6, None, RollType::Regular, Operation::Addition);
+    /// let result = dice.roll_dice_with(&mut *roller);
+    /// assert_eq!(result.result, 2);
+    /// ```
+    ///
+    /// # Panics
+    /// Will not panic, advantage and disadvantage rolls always produce a second set of results.
+    pub fn roll_dice_with(&self, roller: &mut dyn DieRoller) -> RollResult {
         let current_roll_set_size = self.number_of_dice_to_roll as usize;
         let mut first_roll_results: Vec<u32> = Vec::with_capacity(current_roll_set_size);
         for _ in 0..self.number_of_dice_to_roll {
-            first_roll_results.push(rng.gen_range(1..=self.sides));
+            first_roll_results.push(self.roll_single_dice(roller));
         }
 
         let second_roll_results: Option<Vec<u32>> = match self.roll_type {
             RollType::Advantage | RollType::Disadvantage => {
                 let mut second_roll_results: Vec<u32> = Vec::with_capacity(current_roll_set_size);
                 for _ in 0..self.number_of_dice_to_roll {
-                    second_roll_results.push(rng.gen_range(1..=self.sides));
+                    second_roll_results.push(self.roll_single_dice(roller));
                 }
                 Some(second_roll_results)
             }
@@ -138,36 +454,200 @@ impl Dice {
         };
         // Wrapping is unlikely unless a huge (d2^32) dice is used or a huge (d^32) number of dice are used.
         let result = match self.roll_type {
-            RollType::Regular => {
-                first_roll_results.iter().sum::<u32>() as i32 + self.modifier.unwrap_or(0)
-            }
+            RollType::Regular => self.sum(&first_roll_results) + self.modifier.unwrap_or(0),
             RollType::Advantage => {
                 let modifier = self.modifier.unwrap_or(0);
-                let first_result = first_roll_results.iter().sum::<u32>() as i32;
-                let second_result = second_roll_results
-                    .as_ref()
-                    .expect("Expect advantage roll to have second roll results")
-                    .iter()
-                    .sum::<u32>() as i32;
+                let first_result = self.sum(&first_roll_results);
+                let second_result = self.sum(
+                    second_roll_results
+                        .as_ref()
+                        .expect("Expect advantage roll to have second roll results"),
+                );
                 max(first_result + modifier, second_result + modifier)
             }
             RollType::Disadvantage => {
                 let modifier = self.modifier.unwrap_or(0);
-                let first_result = first_roll_results.iter().sum::<u32>() as i32;
-                let second_result = second_roll_results
-                    .as_ref()
-                    .expect("Expect disadvantage roll to have second roll results")
-                    .iter()
-                    .sum::<u32>() as i32;
+                let first_result = self.sum(&first_roll_results);
+                let second_result = self.sum(
+                    second_roll_results
+                        .as_ref()
+                        .expect("Expect disadvantage roll to have second roll results"),
+                );
                 min(first_result + modifier, second_result + modifier)
             }
         };
 
         RollResult::new(first_roll_results, second_roll_results, result)
+            .with_kind(self.kind.clone())
+    }
+
+    /// Calculates the exact probability of each result this dice can produce, including the modifier and any advantage or disadvantage.
+    /// The `operation` is not applied, as it only has meaning as part of a `DiceSet`.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::dice::{Dice, RollType, Operation};
+    ///
+    /// let dice = Dice::new(1, 20, None, RollType::Advantage, Operation::Addition);
+    /// let distribution = dice.distribution();
+    /// assert!((distribution.probability(20) - 0.0975).abs() < 1e-9);
+    /// ```
+    #[must_use]
+    pub fn distribution(&self) -> Distribution {
+        let modifier = self.modifier.unwrap_or(0);
+        let first_faces = Distribution::uniform(self.first_roll_faces());
+        let single_dice = if self.explodes() {
+            #[allow(clippy::cast_possible_wrap)]
+            first_faces.explode(self.sides as i32)
+        } else {
+            #[allow(clippy::cast_sign_loss)]
+            first_faces.map(|face| self.kind.face_value(face as u32))
+        };
+        let dice = self.number_of_dice_to_roll;
+        let kept = match self.keep {
+            Keep::All => single_dice.repeat(dice),
+            Keep::Highest(keep) => single_dice.keep_highest(dice, keep),
+            Keep::Lowest(keep) => single_dice
+                .map(|value| -value)
+                .keep_highest(dice, keep)
+                .map(|value| -value),
+        };
+        let single_roll = kept.map(|total| total + modifier);
+        match self.roll_type {
+            RollType::Regular => single_roll,
+            RollType::Advantage => single_roll.highest_of_two(),
+            RollType::Disadvantage => single_roll.lowest_of_two(),
+        }
+    }
+
+    /// The equally likely faces of the first roll of a single dice, after any reroll.
+    #[allow(clippy::cast_possible_wrap)]
+    fn first_roll_faces(&self) -> Vec<i32> {
+        let faces = 1..=self.sides as i32;
+        match self.reroll {
+            Some(reroll) => faces
+                .clone()
+                .flat_map(|face| {
+                    // A rerolled face is equally likely to become any face
+                    if face <= reroll as i32 {
+                        faces.clone().collect::<Vec<i32>>()
+                    } else {
+                        vec![face; self.sides as usize]
+                    }
+                })
+                .collect(),
+            None => faces.collect(),
+        }
+    }
+
+    /// Rolls one dice, including any reroll and explosions.
+    fn roll_single_dice(&self, roller: &mut dyn DieRoller) -> u32 {
+        let mut roll = roller.roll_die(self.sides);
+        if self.reroll.is_some_and(|reroll| roll <= reroll) {
+            roll = roller.roll_die(self.sides);
+        }
+        let mut total = roll;
+        while self.explodes() && roll == self.sides {
+            roll = roller.roll_die(self.sides);
+            total += roll;
+        }
+        total
+    }
+
+    fn explodes(&self) -> bool {
+        self.explode && self.kind == DiceKind::Standard && self.sides > 1
+    }
+
+    fn sum(&self, rolls: &[u32]) -> i32 {
+        let mut values: Vec<i32> = rolls
+            .iter()
+            .map(|face| self.kind.face_value(*face))
+            .collect();
+        match self.keep {
+            Keep::All => values.iter().sum(),
+            Keep::Highest(keep) => {
+                values.sort_unstable_by(|a, b| b.cmp(a));
+                values.iter().take(keep as usize).sum()
+            }
+            Keep::Lowest(keep) => {
+                values.sort_unstable();
+                values.iter().take(keep as usize).sum()
+            }
+        }
+    }
+}
+
+/// Formats the dice in dice notation e.g. `2d20 + 1 a`, `4dF` or `7k3e`, without its `operation`.
+/// Exploding `!`, kept `kh`/`kl` and rerolled `r` dice which are not a roll and keep roll use common notation which cannot be parsed.
+///
+/// # Examples
+/// ```
+/// use dnd_dice_roller::dice::{Dice, RollType, Operation};
+///
+/// let dice = Dice::new(1, 20, Some(5), RollType::Advantage, Operation::Addition);
+/// assert_eq!(dice.to_string(), "d20 + 5 a");
+/// let dice = Dice::new(4, 6, None, RollType::Regular, Operation::Addition).keep_highest(3);
+/// assert_eq!(dice.to_string(), "4d6kh3");
+/// ```
+impl fmt::Display for Dice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let count = self.number_of_dice_to_roll;
+        let is_roll_and_keep = self.kind == DiceKind::Standard
+            && self.sides == 10
+            && self.explode
+            && matches!(self.keep, Keep::Highest(_))
+            && self.reroll.is_none_or(|face| face == 1);
+        match (&self.kind, self.keep) {
+            (DiceKind::Standard, Keep::Highest(kept)) if is_roll_and_keep => {
+                write!(f, "{count}k{kept}")?;
+                if self.reroll.is_some() {
+                    write!(f, "e")?;
+                }
+            }
+            (kind, keep) => {
+                if count != 1 {
+                    write!(f, "{count}")?;
+                }
+                match kind {
+                    DiceKind::Standard => write!(f, "d{}", self.sides)?,
+                    DiceKind::Fudge => write!(f, "dF")?,
+                    DiceKind::Custom(faces) => {
+                        let faces: Vec<String> = faces.iter().map(ToString::to_string).collect();
+                        write!(f, "d{{{}}}", faces.join(","))?;
+                    }
+                }
+                if self.explode {
+                    write!(f, "!")?;
+                }
+                match keep {
+                    Keep::All => {}
+                    Keep::Highest(kept) => write!(f, "kh{kept}")?,
+                    Keep::Lowest(kept) => write!(f, "kl{kept}")?,
+                }
+                if let Some(face) = self.reroll {
+                    write!(f, "r{face}")?;
+                }
+            }
+        }
+        match self.modifier {
+            Some(modifier) if modifier < 0 => write!(f, " - {}", modifier.unsigned_abs())?,
+            Some(modifier) => write!(f, " + {modifier}")?,
+            None => {}
+        }
+        match self.roll_type {
+            RollType::Regular => Ok(()),
+            RollType::Advantage => write!(f, " a"),
+            RollType::Disadvantage => write!(f, " d"),
+        }
     }
 }
 
 #[cfg(test)]
+#[allow(
+    clippy::manual_assert,
+    clippy::semicolon_if_nothing_returned,
+    clippy::unnecessary_literal_unwrap
+)]
 mod tests {
     use super::*;
     use rand::SeedableRng;
@@ -227,6 +707,143 @@ mod tests {
         assert_eq!(result.first_roll, vec![2, 6, 5]);
     }
 
+    #[test]
+    fn distribution_of_three_d6_plus_two() {
+        let dice = Dice::new(3, 6, Some(2), RollType::Regular, Operation::Addition);
+        let distribution = dice.distribution();
+        assert_eq!(distribution.min(), Some(5));
+        assert_eq!(distribution.max(), Some(20));
+        assert!((distribution.probability(5) - 1.0 / 216.0).abs() < 1e-9);
+        assert!((distribution.mean() - 12.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn distribution_with_disadvantage_favours_low_results() {
+        let dice = Dice::new(1, 20, None, RollType::Disadvantage, Operation::Addition);
+        let distribution = dice.distribution();
+        assert!((distribution.probability(1) - 39.0 / 400.0).abs() < 1e-9);
+        assert!((distribution.probability(20) - 1.0 / 400.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn custom_dice_sum_face_values() {
+        let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let faces = vec![
+            Face::Number(10),
+            Face::Number(20),
+            Face::Symbol("skull".to_string()),
+            Face::Number(40),
+            Face::Number(50),
+            Face::Number(60),
+        ];
+        let dice = Dice::custom(3, faces, Some(1), RollType::Regular, Operation::Addition)
+            .expect("Faces given");
+        let result = dice.roll_dice_from_rng(rng);
+        assert_eq!(result.first_roll, vec![2, 6, 5]);
+        assert_eq!(result.result, 20 + 60 + 50 + 1);
+    }
+
+    #[test]
+    fn custom_dice_symbols_count_as_zero() {
+        let faces = vec![Face::Symbol("blank".to_string()), Face::Number(2)];
+        let dice = Dice::custom(1, faces, None, RollType::Regular, Operation::Addition)
+            .expect("Faces given");
+        let distribution = dice.distribution();
+        assert!((distribution.probability(0) - 0.5).abs() < 1e-9);
+        assert!((distribution.probability(2) - 0.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn custom_dice_need_faces() {
+        let dice = Dice::custom(1, vec![], None, RollType::Regular, Operation::Addition);
+        assert!(matches!(dice, Err(DiceError::NoFaces)));
+        let mut named_dice = NamedDice::new();
+        assert!(matches!(
+            named_dice.insert("empty", vec![]),
+            Err(DiceError::NoFaces)
+        ));
+        assert!(named_dice.get("empty").is_none());
+    }
+
+    #[test]
+    fn keeps_highest_and_lowest_dice() {
+        let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let dice = Dice::new(3, 6, None, RollType::Regular, Operation::Addition).keep_highest(2);
+        let result = dice.roll_dice_from_rng(rng);
+        assert_eq!(result.first_roll, vec![2, 6, 5]);
+        assert_eq!(result.result, 11);
+
+        let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let dice = Dice::new(3, 6, None, RollType::Regular, Operation::Addition).keep_lowest(1);
+        assert_eq!(dice.roll_dice_from_rng(rng).result, 2);
+    }
+
+    #[test]
+    fn keep_distribution_matches_enumeration() {
+        let dice = Dice::new(4, 6, None, RollType::Regular, Operation::Addition).keep_highest(3);
+        let distribution = dice.distribution();
+        let mut counts = [0_u32; 19];
+        for a in 1..=6 {
+            for b in 1..=6 {
+                for c in 1..=6 {
+                    for d in 1..=6 {
+                        let lowest = *[a, b, c, d].iter().min().unwrap();
+                        counts[a + b + c + d - lowest] += 1;
+                    }
+                }
+            }
+        }
+        for (total, count) in counts.iter().enumerate().skip(3) {
+            let expected = f64::from(*count) / 1296.0;
+            #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+            let probability = distribution.probability(total as i32);
+            assert!((probability - expected).abs() < 1e-9);
+        }
+
+        let dice = Dice::new(2, 20, None, RollType::Regular, Operation::Addition).keep_lowest(1);
+        let disadvantage = Dice::new(1, 20, None, RollType::Disadvantage, Operation::Addition);
+        assert!((dice.distribution().mean() - disadvantage.distribution().mean()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn rerolls_low_faces_once() {
+        let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let dice = Dice::new(1, 6, None, RollType::Regular, Operation::Addition).rerolling(2);
+        // The first roll of 2 is rerolled into a 6
+        assert_eq!(dice.roll_dice_from_rng(rng).result, 6);
+
+        let distribution = dice.distribution();
+        assert!((distribution.probability(1) - 2.0 / 36.0).abs() < 1e-9);
+        assert!((distribution.probability(6) - 8.0 / 36.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn exploding_dice_add_rerolls_of_the_highest_face() {
+        let mut rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let dice = Dice::new(1, 2, None, RollType::Regular, Operation::Addition).exploding();
+        for _ in 0..100 {
+            let result = dice.roll_dice_from_rng(&mut rng);
+            // An exploding d2 can only stop on a 1, so every total is odd
+            assert_eq!(result.result % 2, 1);
+        }
+    }
+
+    #[test]
+    fn exploding_dice_distribution() {
+        let dice = Dice::new(1, 6, None, RollType::Regular, Operation::Addition).exploding();
+        let distribution = dice.distribution();
+        assert!((distribution.probability(5) - 1.0 / 6.0).abs() < 1e-9);
+        assert!(distribution.probability(6).abs() < 1e-9);
+        assert!((distribution.probability(7) - 1.0 / 36.0).abs() < 1e-9);
+        assert!((distribution.mean() - 4.2).abs() < 1e-6);
+    }
+
+    #[test]
+    fn only_standard_dice_explode() {
+        let dice = Dice::fudge(1, None, RollType::Regular, Operation::Addition).exploding();
+        assert_eq!(dice.distribution().max(), Some(1));
+    }
+
     #[test]
     fn roll_dice_within_range_simple() {
         let dice = Dice::new(1, 20, None, RollType::Regular, Operation::Addition);
@@ -267,4 +884,38 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn formats_dice_notation() {
+        let regular = RollType::Regular;
+        let add = Operation::Addition;
+        let faces = vec![Face::Number(2), Face::Symbol("skull".to_string())];
+        let cases = [
+            (Dice::new(1, 6, None, regular, add), "d6"),
+            (
+                Dice::new(2, 20, Some(-1), RollType::Disadvantage, add),
+                "2d20 - 1 d",
+            ),
+            (Dice::fudge(4, Some(2), regular, add), "4dF + 2"),
+            (
+                Dice::custom(2, faces, None, regular, add).expect("Faces given"),
+                "2d{2,skull}",
+            ),
+            (Dice::roll_and_keep(7, 3, Some(5), regular, add), "7k3 + 5"),
+            (
+                Dice::roll_and_keep(7, 3, None, regular, add).rerolling(1),
+                "7k3e",
+            ),
+            (
+                Dice::new(2, 6, None, regular, add)
+                    .exploding()
+                    .keep_lowest(1),
+                "2d6!kl1",
+            ),
+            (Dice::new(1, 8, None, regular, add).rerolling(2), "d8r2"),
+        ];
+        for (dice, expected) in cases {
+            assert_eq!(dice.to_string(), expected);
+        }
+    }
 }
@@ -1,7 +1,15 @@
-use std::fmt;
+use crate::dice::{DiceKind, Face};
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
 
 /// Represents the result of rolling (a set of) `Dice`.
-#[derive(PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct DiceSetResults {
     /// The actual results of the dice that were cast
     pub dice_results: Vec<RollResult>,
@@ -18,13 +26,17 @@ impl DiceSetResults {
     }
 }
 
-#[derive(PartialEq, Debug)]
+#[derive(Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RollResult {
     /// Present on `RollType::Advantage`, `RollType::Disadvantage` and `RollType::Regular` rolls.
     pub first_roll: Vec<u32>,
     /// Only present on `RollType::Advantage`, `RollType::Disadvantage` rolls.
     pub second_roll: Option<Vec<u32>>,
     pub result: i32,
+    /// What the faces of the dice rolled show. The rolls hold the 1-based position of the face rolled, see `DiceKind::face_value`.
+    /// For exploding dice the rolls hold the total of each dice, including its explosions.
+    pub kind: DiceKind,
 }
 
 impl RollResult {
@@ -33,6 +45,63 @@ impl RollResult {
             first_roll,
             second_roll,
             result,
+            kind: DiceKind::Standard,
+        }
+    }
+
+    pub(crate) fn with_kind(mut self, kind: DiceKind) -> Self {
+        self.kind = kind;
+        self
+    }
+
+    /// The faces shown by the dice of the first roll.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::str::FromStr;
+    /// use dnd_dice_roller::dice::Face;
+    /// use dnd_dice_roller::roll::Roll;
+    /// # use dnd_dice_roller::error::DiceError;
+    ///
+    /// let roll = Roll::from_str("2d{skull,skull}")?;
+    /// let result = roll.roll();
+    /// let skull = Face::Symbol("skull".to_string());
+    /// assert_eq!(result[0].dice_results[0].first_faces(), vec![skull.clone(), skull]);
+    /// # Ok::<(), DiceError>(())
+    /// ```
+    #[must_use]
+    pub fn first_faces(&self) -> Vec<Face> {
+        self.faces(&self.first_roll)
+    }
+
+    /// The faces shown by the dice of the second roll. Only present on `RollType::Advantage`, `RollType::Disadvantage` rolls.
+    #[must_use]
+    pub fn second_faces(&self) -> Option<Vec<Face>> {
+        self.second_roll.as_ref().map(|roll| self.faces(roll))
+    }
+
+    fn faces(&self, roll: &[u32]) -> Vec<Face> {
+        roll.iter().map(|face| self.kind.face(*face)).collect()
+    }
+
+    fn format_roll(&self, roll: &[u32]) -> String {
+        match self.kind {
+            DiceKind::Standard => format!("{roll:?}"),
+            DiceKind::Custom(_) => {
+                let faces: Vec<String> = self.faces(roll).iter().map(Face::to_string).collect();
+                format!("[{}]", faces.join(", "))
+            }
+            DiceKind::Fudge => {
+                let symbols: Vec<&str> = roll
+                    .iter()
+                    .map(|face| match self.kind.face_value(*face) {
+                        value if value > 0 => "+",
+                        value if value < 0 => "-",
+                        _ => " ",
+                    })
+                    .collect();
+                format!("[{}]", symbols.join(", "))
+            }
         }
     }
 }
@@ -40,13 +109,19 @@ impl RollResult {
 impl fmt::Display for RollResult {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match &self.second_roll {
-            None => write!(f, "{:?}", self.first_roll),
-            Some(second_roll) => write!(f, "[{:?}, {:?}]", self.first_roll, second_roll),
+            None => write!(f, "{}", self.format_roll(&self.first_roll)),
+            Some(second_roll) => write!(
+                f,
+                "[{}, {}]",
+                self.format_roll(&self.first_roll),
+                self.format_roll(second_roll)
+            ),
         }
     }
 }
 
 #[cfg(test)]
+#[allow(clippy::uninlined_format_args)]
 mod test {
     use super::*;
 
@@ -61,4 +136,28 @@ mod test {
         let raw_result = RollResult::new(vec![4, 2, 1, 3], Some(vec![5, 2, 3, 4]), 14);
         assert_eq!("[[4, 2, 1, 3], [5, 2, 3, 4]]", format!("{}", raw_result));
     }
+
+    #[test]
+    fn format_fudge_result_as_symbols() {
+        let raw_result = RollResult::new(vec![3, 1, 2, 3], None, 1).with_kind(DiceKind::Fudge);
+        assert_eq!("[+, -,  , +]", format!("{raw_result}"));
+    }
+
+    #[test]
+    fn format_custom_result_as_faces() {
+        let kind = DiceKind::Custom(vec![Face::Number(2), Face::Symbol("skull".to_string())]);
+        let raw_result = RollResult::new(vec![2, 1], None, 2).with_kind(kind);
+        assert_eq!("[skull, 2]", format!("{raw_result}"));
+        assert_eq!(
+            raw_result.first_faces(),
+            vec![Face::Symbol("skull".to_string()), Face::Number(2)]
+        );
+    }
+
+    #[test]
+    fn format_fudge_result_with_two_rolls() {
+        let raw_result =
+            RollResult::new(vec![3, 2], Some(vec![1, 1]), 1).with_kind(DiceKind::Fudge);
+        assert_eq!("[[+,  ], [-, -]]", format!("{raw_result}"));
+    }
 }
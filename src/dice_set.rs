@@ -1,11 +1,15 @@
 use crate::{
     dice::{Dice, Operation},
     dice_result::{DiceSetResults, RollResult},
+    die_roller::DieRoller,
+    distribution::Distribution,
 };
 
+use alloc::vec::Vec;
+use core::fmt;
 use rand::Rng;
 
-#[derive(Debug)]
+#[derive(PartialEq, Debug)]
 /// Represents a set of non-homogenous dice e.g. d20+2 + d4.
 pub struct DiceSet {
     dice: Vec<Dice>,
@@ -13,10 +17,17 @@ pub struct DiceSet {
 
 impl DiceSet {
     /// Create a new dice set
+    #[must_use]
     pub fn new(dice: Vec<Dice>) -> Self {
         DiceSet { dice }
     }
 
+    /// The dice in the set.
+    #[must_use]
+    pub fn dice(&self) -> &[Dice] {
+        &self.dice
+    }
+
     /// Rolls a set of dice and produces a `DiceSetResults`. Using underlying OS RNG for the dice roll.
     ///
     /// # Examples
@@ -28,6 +39,7 @@ impl DiceSet {
     /// let dice_set = DiceSet::new(dice);
     /// let result = dice_set.roll_dice_set();
     /// ```
+    #[cfg(feature = "std")]
     #[must_use]
     pub fn roll_dice_set(&self) -> DiceSetResults {
         let mut rng = rand::thread_rng();
@@ -48,13 +60,33 @@ impl DiceSet {
     /// let result = dice_set.roll_dice_set_from_rng(rng);
     /// assert_eq!(result.final_result, 14);
     /// ```
-    #[allow(clippy::cast_possible_wrap)]
+    ///
+    /// # Panics
+    /// Will not panic, every result has a corresponding `Dice`.
     pub fn roll_dice_set_from_rng<R: Rng + Sized>(&self, mut rng: R) -> DiceSetResults {
-        let results: Vec<RollResult> = self
-            .dice
-            .iter()
-            .map(|d| d.roll_dice_from_rng(&mut rng))
-            .collect();
+        self.roll_dice_set_with(&mut rng)
+    }
+
+    /// Rolls a set of dice and produces a `DiceSetResults`. Uses a `DieRoller` passed in, which can be chosen at runtime.
+    ///
+    /// # Examples
+    /// ```
+    /// use rand::SeedableRng;
+    /// use dnd_dice_roller::dice::{Dice, RollType, Operation};
+    /// use dnd_dice_roller::dice_set::DiceSet;
+    ///
+    /// let mut rng = rand_pcg::Pcg64Mcg::seed_from_u64(42);
+    /// let dice = vec![Dice::new(3, 6, Some(1), RollType::Regular, Operation::Addition)];
+    /// let dice_set = DiceSet::new(dice);
+    /// let result = dice_set.roll_dice_set_with(&mut rng);
+    /// assert_eq!(result.final_result, 14);
+    /// ```
+    ///
+    /// # Panics
+    /// Will not panic, every result has a corresponding `Dice`.
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn roll_dice_set_with(&self, roller: &mut dyn DieRoller) -> DiceSetResults {
+        let results: Vec<RollResult> = self.dice.iter().map(|d| d.roll_dice_with(roller)).collect();
         let total = results.iter().enumerate().fold(0, |acc, (index, roll)| {
             match self.dice.get(index).unwrap().operation {
                 Operation::Addition => acc + roll.result,
@@ -64,6 +96,50 @@ impl DiceSet {
 
         DiceSetResults::new(results, total)
     }
+
+    /// Calculates the exact probability of each final result this set of dice can produce.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::dice::{Dice, RollType, Operation};
+    /// use dnd_dice_roller::dice_set::DiceSet;
+    ///
+    /// let dice_set = DiceSet::new(vec![
+    ///     Dice::new(1, 6, None, RollType::Regular, Operation::Addition),
+    ///     Dice::new(1, 4, None, RollType::Regular, Operation::Subtraction),
+    /// ]);
+    /// let distribution = dice_set.distribution();
+    /// assert_eq!(distribution.min(), Some(-3));
+    /// assert_eq!(distribution.max(), Some(5));
+    /// ```
+    #[must_use]
+    pub fn distribution(&self) -> Distribution {
+        self.dice
+            .iter()
+            .fold(Distribution::constant(0), |acc, dice| {
+                let distribution = dice.distribution();
+                match dice.operation {
+                    Operation::Addition => acc.add(&distribution),
+                    Operation::Subtraction => acc.add(&distribution.map(|value| -value)),
+                }
+            })
+    }
+}
+
+/// Formats the dice set in dice notation e.g. `d20 + 2 + d4`.
+impl fmt::Display for DiceSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, dice) in self.dice.iter().enumerate() {
+            match (index, dice.operation) {
+                (0, Operation::Addition) => {}
+                (0, Operation::Subtraction) => write!(f, "-")?,
+                (_, Operation::Addition) => write!(f, " + ")?,
+                (_, Operation::Subtraction) => write!(f, " - ")?,
+            }
+            write!(f, "{dice}")?;
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -76,6 +152,18 @@ mod test {
 
     const SEED: u64 = 42;
 
+    #[test]
+    fn distribution_applies_operations() {
+        let dice_set = DiceSet::new(vec![
+            Dice::new(2, 6, Some(1), RollType::Regular, Operation::Addition),
+            Dice::new(1, 6, None, RollType::Regular, Operation::Subtraction),
+        ]);
+        let distribution = dice_set.distribution();
+        assert_eq!(distribution.min(), Some(-3));
+        assert_eq!(distribution.max(), Some(12));
+        assert!((distribution.mean() - 4.5).abs() < 1e-9);
+    }
+
     #[test]
     fn produces_predictable_results_one_d6_parsed_equals_two() {
         let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
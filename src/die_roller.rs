@@ -0,0 +1,87 @@
+use rand::RngCore;
+
+/// Represents a source of dice rolls. Unlike `rand::Rng` it is object safe, so the source can be chosen at runtime
+/// and passed around as a `&mut dyn DieRoller` or a `Box<dyn DieRoller>`.
+///
+/// Every `rand::RngCore` is a `DieRoller`, rolling with `gen_range(1..=sides)` exactly as the `_from_rng` methods do.
+///
+/// # Examples
+/// ```
+/// use rand::SeedableRng;
+/// use std::str::FromStr;
+/// use dnd_dice_roller::die_roller::DieRoller;
+/// use dnd_dice_roller::roll::Roll;
+/// use dnd_dice_roller::scripted_rng::ScriptedRng;
+/// # use dnd_dice_roller::error::DiceError;
+///
+/// let scripted = true;
+/// let mut roller: Box<dyn DieRoller> = if scripted {
+///     Box::new(ScriptedRng::new(20, &[20]))
+/// } else {
+///     Box::new(rand_pcg::Pcg64Mcg::seed_from_u64(42))
+/// };
+/// let results = Roll::from_str("d20 + 2")?.roll_with(&mut *roller);
+/// assert_eq!(results[0].final_result, 22);
+/// # Ok::<(), DiceError>(())
+/// ```
+pub trait DieRoller {
+    /// Rolls a single die, returning a face from 1 to the number of sides.
+    fn roll_die(&mut self, sides: u32) -> u32;
+}
+
+impl<R: RngCore + ?Sized> DieRoller for R {
+    fn roll_die(&mut self, sides: u32) -> u32 {
+        rand::Rng::gen_range(self, 1..=sides)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::dice::{Dice, Operation, RollType};
+    use crate::roll::Roll;
+    use rand::SeedableRng;
+    use std::str::FromStr;
+
+    const SEED: u64 = 42;
+
+    struct HighestRoller;
+
+    impl DieRoller for HighestRoller {
+        fn roll_die(&mut self, sides: u32) -> u32 {
+            sides
+        }
+    }
+
+    #[test]
+    fn rng_adapter_matches_rolling_from_rng() {
+        let roll = Roll::from_str("4d6 + 2 a, 2d{1,2,skull}, 7k3e").expect("Valid roll");
+        let expected = roll.roll_from_rng(rand_pcg::Pcg64Mcg::seed_from_u64(SEED));
+        let mut rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let roller: &mut dyn DieRoller = &mut rng;
+        assert_eq!(roll.roll_with(roller), expected);
+    }
+
+    #[test]
+    fn rolls_with_custom_rollers() {
+        let dice = Dice::new(3, 8, Some(1), RollType::Disadvantage, Operation::Addition);
+        let result = dice.roll_dice_with(&mut HighestRoller);
+        assert_eq!(result.first_roll, vec![8, 8, 8]);
+        assert_eq!(result.result, 25);
+    }
+
+    #[test]
+    fn rolls_with_boxed_rollers() {
+        let mut rollers: Vec<Box<dyn DieRoller>> = vec![
+            Box::new(HighestRoller),
+            Box::new(rand_pcg::Pcg64Mcg::seed_from_u64(SEED)),
+            Box::new(rand::thread_rng()),
+        ];
+        let roll = Roll::from_str("2d20").expect("Valid roll");
+        for roller in &mut rollers {
+            let results = roll.roll_with(&mut **roller);
+            assert!((2..=40).contains(&results[0].final_result));
+        }
+    }
+}
@@ -0,0 +1,262 @@
+use alloc::{collections::BTreeMap, vec::Vec};
+use core::ops::RangeBounds;
+
+/// Explosions less likely than this are left out of the distribution of an exploding dice.
+const EXPLOSION_CUTOFF: f64 = 1e-12;
+
+/// Represents the exact probability of every total a roll can produce.
+/// e.g. A d6 has a `1/6` probability for each of the totals `1` to `6`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Distribution {
+    probabilities: BTreeMap<i32, f64>,
+}
+
+impl Distribution {
+    /// Creates a distribution where a single total is certain.
+    pub(crate) fn constant(value: i32) -> Self {
+        let mut probabilities = BTreeMap::new();
+        probabilities.insert(value, 1.0);
+        Distribution { probabilities }
+    }
+
+    /// Creates a distribution where each of the values provided is equally likely.
+    /// Repeated values are weighted by the number of times they occur.
+    #[allow(clippy::cast_precision_loss)]
+    pub(crate) fn uniform<I: IntoIterator<Item = i32>>(values: I) -> Self {
+        let values: Vec<i32> = values.into_iter().collect();
+        let weight = 1.0 / values.len() as f64;
+        let mut probabilities = BTreeMap::new();
+        for value in values {
+            *probabilities.entry(value).or_insert(0.0) += weight;
+        }
+        Distribution { probabilities }
+    }
+
+    /// Produces the distribution where rolling `max` explodes, adding a dice numbered 1 to `max` which itself explodes.
+    /// The chain of explosions has no end, so it is cut off once further explosions become negligibly unlikely.
+    #[must_use]
+    pub(crate) fn explode(&self, max: i32) -> Self {
+        let weight = 1.0 / f64::from(max);
+        let mut explosion = BTreeMap::new();
+        let mut chance = 1.0;
+        let mut base = max;
+        while chance > EXPLOSION_CUTOFF {
+            for face in 1..max {
+                explosion.insert(base + face, chance * weight);
+            }
+            chance *= weight;
+            base += max;
+        }
+
+        let mut probabilities = BTreeMap::new();
+        for (value, probability) in &self.probabilities {
+            if *value == max {
+                for (total, explosion_probability) in &explosion {
+                    *probabilities.entry(*total).or_insert(0.0) +=
+                        probability * explosion_probability;
+                }
+            } else {
+                *probabilities.entry(*value).or_insert(0.0) += probability;
+            }
+        }
+        Distribution { probabilities }
+    }
+
+    /// Produces the distribution of the sum of a total from this distribution and a total from `other`.
+    #[must_use]
+    pub(crate) fn add(&self, other: &Self) -> Self {
+        let mut probabilities = BTreeMap::new();
+        for (value, probability) in &self.probabilities {
+            for (other_value, other_probability) in &other.probabilities {
+                *probabilities.entry(value + other_value).or_insert(0.0) +=
+                    probability * other_probability;
+            }
+        }
+        Distribution { probabilities }
+    }
+
+    /// Produces the distribution of the sum of `times` independent totals from this distribution.
+    #[must_use]
+    pub(crate) fn repeat(&self, times: u32) -> Self {
+        (0..times).fold(Self::constant(0), |acc, _| acc.add(self))
+    }
+
+    /// Produces the distribution of the sum of the highest `keep` of `times` independent totals from this distribution.
+    /// Works through the totals from highest to lowest, tracking how many totals have been placed and the sum of those kept.
+    #[must_use]
+    #[allow(clippy::cast_possible_wrap)]
+    pub(crate) fn keep_highest(&self, times: u32, keep: u32) -> Self {
+        let mut states: BTreeMap<(u32, i32), f64> = BTreeMap::new();
+        states.insert((0, 0), 1.0);
+        for (value, probability) in self.probabilities.iter().rev() {
+            let mut next = BTreeMap::new();
+            for ((placed, sum), state_probability) in &states {
+                let remaining = times - placed;
+                let mut weight = *state_probability;
+                for count in 0..=remaining {
+                    let kept = count.min(keep.saturating_sub(*placed));
+                    *next
+                        .entry((placed + count, sum + value * kept as i32))
+                        .or_insert(0.0) += weight;
+                    // Choose one more of the remaining totals to also be this value
+                    weight *= probability * f64::from(remaining - count) / f64::from(count + 1);
+                }
+            }
+            states = next;
+        }
+
+        let mut probabilities = BTreeMap::new();
+        for ((placed, sum), probability) in states {
+            if placed == times {
+                *probabilities.entry(sum).or_insert(0.0) += probability;
+            }
+        }
+        Distribution { probabilities }
+    }
+
+    /// Produces the distribution of this distribution with every total transformed by `f`.
+    #[must_use]
+    pub(crate) fn map<F: Fn(i32) -> i32>(&self, f: F) -> Self {
+        let mut probabilities = BTreeMap::new();
+        for (value, probability) in &self.probabilities {
+            *probabilities.entry(f(*value)).or_insert(0.0) += probability;
+        }
+        Distribution { probabilities }
+    }
+
+    /// Produces the distribution of the highest of two independent totals from this distribution.
+    #[must_use]
+    pub(crate) fn highest_of_two(&self) -> Self {
+        let mut probabilities = BTreeMap::new();
+        let mut cumulative = 0.0;
+        for (value, probability) in &self.probabilities {
+            let previous = cumulative;
+            cumulative += probability;
+            probabilities.insert(*value, cumulative * cumulative - previous * previous);
+        }
+        Distribution { probabilities }
+    }
+
+    /// Produces the distribution of the lowest of two independent totals from this distribution.
+    #[must_use]
+    pub(crate) fn lowest_of_two(&self) -> Self {
+        self.map(|value| -value)
+            .highest_of_two()
+            .map(|value| -value)
+    }
+
+    /// The probability of rolling exactly `value`.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::dice::{Dice, RollType, Operation};
+    ///
+    /// let dice = Dice::new(2, 6, None, RollType::Regular, Operation::Addition);
+    /// let distribution = dice.distribution();
+    /// assert!((distribution.probability(7) - 6.0 / 36.0).abs() < 1e-9);
+    /// ```
+    #[must_use]
+    pub fn probability(&self, value: i32) -> f64 {
+        self.probabilities.get(&value).copied().unwrap_or(0.0)
+    }
+
+    /// The probability of rolling a total within `range`.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::dice::{Dice, RollType, Operation};
+    ///
+    /// let dice = Dice::new(1, 20, Some(5), RollType::Regular, Operation::Addition);
+    /// let distribution = dice.distribution();
+    /// // Chance to meet or beat an AC of 15
+    /// assert!((distribution.probability_in_range(15..) - 0.55).abs() < 1e-9);
+    /// ```
+    #[must_use]
+    pub fn probability_in_range<R: RangeBounds<i32>>(&self, range: R) -> f64 {
+        self.probabilities
+            .range(range)
+            .map(|(_, probability)| probability)
+            .sum()
+    }
+
+    /// The expected (mean) total.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::dice::{Dice, RollType, Operation};
+    ///
+    /// let dice = Dice::new(1, 8, Some(2), RollType::Regular, Operation::Addition);
+    /// assert!((dice.distribution().mean() - 6.5).abs() < 1e-9);
+    /// ```
+    #[must_use]
+    pub fn mean(&self) -> f64 {
+        self.probabilities
+            .iter()
+            .map(|(value, probability)| f64::from(*value) * probability)
+            .sum()
+    }
+
+    /// The lowest total that can be rolled.
+    #[must_use]
+    pub fn min(&self) -> Option<i32> {
+        self.probabilities.keys().next().copied()
+    }
+
+    /// The highest total that can be rolled.
+    #[must_use]
+    pub fn max(&self) -> Option<i32> {
+        self.probabilities.keys().next_back().copied()
+    }
+
+    /// Iterates over each possible total and its probability, from lowest to highest total.
+    pub fn iter(&self) -> impl Iterator<Item = (i32, f64)> + '_ {
+        self.probabilities
+            .iter()
+            .map(|(value, probability)| (*value, *probability))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const EPSILON: f64 = 1e-9;
+
+    #[test]
+    fn uniform_distribution_has_equal_probabilities() {
+        let distribution = Distribution::uniform(1..=4);
+        for value in 1..=4 {
+            assert!((distribution.probability(value) - 0.25).abs() < EPSILON);
+        }
+        assert!(distribution.probability(5).abs() < EPSILON);
+    }
+
+    #[test]
+    fn adding_two_d6_peaks_at_seven() {
+        let d6 = Distribution::uniform(1..=6);
+        let distribution = d6.add(&d6);
+        assert_eq!(distribution.min(), Some(2));
+        assert_eq!(distribution.max(), Some(12));
+        assert!((distribution.probability(7) - 6.0 / 36.0).abs() < EPSILON);
+        assert!((distribution.probability(2) - 1.0 / 36.0).abs() < EPSILON);
+        assert!((distribution.mean() - 7.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn highest_and_lowest_of_two_d20() {
+        let d20 = Distribution::uniform(1..=20);
+        let highest = d20.highest_of_two();
+        let lowest = d20.lowest_of_two();
+        assert!((highest.probability(20) - 39.0 / 400.0).abs() < EPSILON);
+        assert!((highest.probability(1) - 1.0 / 400.0).abs() < EPSILON);
+        assert!((lowest.probability(1) - 39.0 / 400.0).abs() < EPSILON);
+        assert!((highest.probability_in_range(..) - 1.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn map_merges_totals() {
+        let distribution = Distribution::uniform(-1..=2).map(|value| value.max(1));
+        assert!((distribution.probability(1) - 0.75).abs() < EPSILON);
+        assert!((distribution.probability(2) - 0.25).abs() < EPSILON);
+    }
+}
@@ -1,10 +1,159 @@
-use dice_command_parser::error::ParserError;
-use thiserror::Error;
+use alloc::string::String;
+use core::fmt;
 
-#[derive(Error, Debug)]
+#[derive(Debug)]
 pub enum DiceError {
-    #[error("Error parsing input: {0}")]
-    ParseError(#[from] ParserError),
-    #[error("An unknown error occurred")]
+    ParseError(String),
+    TableGap {
+        table: String,
+        start: i32,
+        end: i32,
+    },
+    TableOverlap {
+        table: String,
+        value: i32,
+    },
+    TableOutOfRange {
+        table: String,
+        start: i32,
+        end: i32,
+    },
+    TableRollUncovered {
+        table: String,
+        value: i32,
+    },
+    TableWithoutResults(String),
+    UnknownTable(String),
+    TableRecursion(String),
+    TableFile {
+        file: String,
+        line: usize,
+        reason: String,
+    },
+    #[cfg(feature = "std")]
+    TableFileRead {
+        file: String,
+        source: std::io::Error,
+    },
+    OutcomeOverlap {
+        first: String,
+        second: String,
+        value: i32,
+    },
+    InvalidServerSeed(String),
+    CommitmentMismatch(String),
+    ResultMismatch(u64),
+    UnsupportedRng(String),
+    ReplayMismatch(String),
+    Journal {
+        line: usize,
+        reason: String,
+    },
+    #[cfg(feature = "std")]
+    JournalIo(std::io::Error),
+    InvalidFace {
+        face: u32,
+        sides: u32,
+    },
+    MissingFaces(usize),
+    UnusedFaces(usize),
+    NoFaces,
     Unknown,
 }
+
+impl fmt::Display for DiceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiceError::ParseError(error) => write!(f, "Error parsing input: {error}"),
+            DiceError::TableGap { table, start, end } => {
+                write!(
+                    f,
+                    "Table {table} has no entry for the results {start} to {end}"
+                )
+            }
+            DiceError::TableOverlap { table, value } => {
+                write!(
+                    f,
+                    "Table {table} has more than one entry for the result {value}"
+                )
+            }
+            DiceError::TableOutOfRange { table, start, end } => write!(
+                f,
+                "Table {table} has an entry for the results {start} to {end} which cannot be rolled"
+            ),
+            DiceError::TableRollUncovered { table, value } => {
+                write!(
+                    f,
+                    "Table {table} has no entry for the rolled result {value}"
+                )
+            }
+            DiceError::TableWithoutResults(table) => {
+                write!(f, "Table {table} has no entries which produce a result")
+            }
+            DiceError::UnknownTable(table) => write!(f, "No table named {table} exists"),
+            DiceError::TableRecursion(table) => write!(
+                f,
+                "Table {table} references other tables too deeply, it may reference itself"
+            ),
+            DiceError::TableFile { file, line, reason } => {
+                write!(f, "Error in table {file} on line {line}: {reason}")
+            }
+            #[cfg(feature = "std")]
+            DiceError::TableFileRead { file, source } => {
+                write!(f, "Unable to read table file {file}: {source}")
+            }
+            DiceError::OutcomeOverlap {
+                first,
+                second,
+                value,
+            } => write!(
+                f,
+                "Outcomes {first} and {second} both include the result {value}"
+            ),
+            DiceError::InvalidServerSeed(seed) => {
+                write!(f, "Server seed must be 64 hexadecimal characters: {seed}")
+            }
+            DiceError::CommitmentMismatch(commitment) => {
+                write!(f, "Server seed does not match the commitment {commitment}")
+            }
+            DiceError::ResultMismatch(nonce) => write!(
+                f,
+                "Results of the roll with nonce {nonce} do not match the revealed seeds"
+            ),
+            DiceError::UnsupportedRng(rng) => write!(f, "Unsupported RNG algorithm {rng}"),
+            DiceError::ReplayMismatch(expression) => write!(
+                f,
+                "Replaying {expression} did not reproduce the recorded results"
+            ),
+            DiceError::Journal { line, reason } => {
+                write!(f, "Error in roll journal on line {line}: {reason}")
+            }
+            #[cfg(feature = "std")]
+            DiceError::JournalIo(error) => {
+                write!(f, "Unable to read or write roll journal: {error}")
+            }
+            DiceError::InvalidFace { face, sides } => write!(f, "A d{sides} cannot roll a {face}"),
+            DiceError::MissingFaces(faces) => write!(
+                f,
+                "All {faces} faces were used before every dice was rolled"
+            ),
+            DiceError::UnusedFaces(faces) => write!(
+                f,
+                "{faces} faces were left over after every dice was rolled"
+            ),
+            DiceError::NoFaces => write!(f, "A custom dice must have at least one face"),
+            DiceError::Unknown => write!(f, "An unknown error occurred"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for DiceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DiceError::TableFileRead { source, .. } => Some(source),
+            DiceError::JournalIo(error) => Some(error),
+            _ => None,
+        }
+    }
+}
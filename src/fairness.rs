@@ -0,0 +1,322 @@
+use crate::{dice_result::DiceSetResults, error::DiceError, roll::Roll};
+
+use rand::{Rng, SeedableRng};
+use rand_pcg::Pcg64Mcg;
+use sha2::{Digest, Sha256};
+use std::fmt::Write;
+
+/// The number of bytes in a server seed.
+const SEED_LENGTH: usize = 32;
+
+/// Represents the secret seed a server commits to before rolling, and reveals once it is finished with it.
+/// Only the commitment should be shared until the seed is revealed.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ServerSeed([u8; SEED_LENGTH]);
+
+impl ServerSeed {
+    /// Generates a new random server seed.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::fairness::ServerSeed;
+    ///
+    /// let server_seed = ServerSeed::generate();
+    /// assert_ne!(server_seed, ServerSeed::generate());
+    /// ```
+    #[must_use]
+    pub fn generate() -> Self {
+        ServerSeed(rand::thread_rng().gen())
+    }
+
+    /// Reads a revealed server seed from its hexadecimal form.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::fairness::ServerSeed;
+    /// # use dnd_dice_roller::error::DiceError;
+    ///
+    /// let server_seed = ServerSeed::generate();
+    /// assert_eq!(ServerSeed::from_hex(&server_seed.to_hex())?, server_seed);
+    /// # Ok::<(), DiceError>(())
+    /// ```
+    /// # Errors
+    /// Errors with `DiceError::InvalidServerSeed` if the input is not 64 hexadecimal characters.
+    pub fn from_hex(input: &str) -> Result<Self, DiceError> {
+        let invalid = || DiceError::InvalidServerSeed(input.to_string());
+        if input.len() != SEED_LENGTH * 2 || !input.is_ascii() {
+            return Err(invalid());
+        }
+        let mut seed = [0; SEED_LENGTH];
+        for (index, byte) in seed.iter_mut().enumerate() {
+            *byte =
+                u8::from_str_radix(&input[index * 2..index * 2 + 2], 16).map_err(|_| invalid())?;
+        }
+        Ok(ServerSeed(seed))
+    }
+
+    /// The server seed in hexadecimal, to be revealed once no more rolls will use it.
+    #[must_use]
+    pub fn to_hex(&self) -> String {
+        to_hex(&self.0)
+    }
+
+    /// The SHA-256 hash of the server seed in hexadecimal, which can be shared before rolling without revealing the seed.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::fairness::ServerSeed;
+    ///
+    /// let commitment = ServerSeed::generate().commitment();
+    /// assert_eq!(commitment.len(), 64);
+    /// ```
+    #[must_use]
+    pub fn commitment(&self) -> String {
+        to_hex(&Sha256::digest(self.0))
+    }
+}
+
+impl std::fmt::Debug for ServerSeed {
+    // Keeps the seed out of logs until it is deliberately revealed
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ServerSeed({})", self.commitment())
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut hex, byte| {
+        let _ = write!(hex, "{byte:02x}");
+        hex
+    })
+}
+
+/// Derives the RNG for a single roll from the server seed, the client seed and the nonce of the roll.
+/// The same inputs always produce the same RNG, so a roll can be recomputed once the server seed is revealed.
+///
+/// # Examples
+/// ```
+/// use std::str::FromStr;
+/// use dnd_dice_roller::fairness::{rng, ServerSeed};
+/// use dnd_dice_roller::roll::Roll;
+/// # use dnd_dice_roller::error::DiceError;
+///
+/// let server_seed = ServerSeed::generate();
+/// let roll = Roll::from_str("d20 + 5")?;
+/// let first = roll.roll_from_rng(rng(&server_seed, "player", 1));
+/// let second = roll.roll_from_rng(rng(&server_seed, "player", 1));
+/// assert_eq!(first, second);
+/// # Ok::<(), DiceError>(())
+/// ```
+#[must_use]
+pub fn rng(server_seed: &ServerSeed, client_seed: &str, nonce: u64) -> Pcg64Mcg {
+    let mut hasher = Sha256::new();
+    hasher.update(server_seed.0);
+    hasher.update(b":");
+    hasher.update(client_seed.as_bytes());
+    hasher.update(b":");
+    hasher.update(nonce.to_string().as_bytes());
+    let hash = hasher.finalize();
+
+    let mut seed = <Pcg64Mcg as SeedableRng>::Seed::default();
+    let length = seed.len();
+    seed.copy_from_slice(&hash[..length]);
+    Pcg64Mcg::from_seed(seed)
+}
+
+/// Represents a single fair roll, with everything needed to verify it other than the server seed.
+#[derive(PartialEq, Debug)]
+pub struct FairRollRecord {
+    /// The nonce the roll was made with.
+    pub nonce: u64,
+    /// The results of the roll.
+    pub results: Vec<DiceSetResults>,
+}
+
+/// Rolls dice fairly for a single client seed, using a new nonce for every roll.
+#[derive(Debug)]
+pub struct FairRoller {
+    server_seed: ServerSeed,
+    client_seed: String,
+    nonce: u64,
+}
+
+impl FairRoller {
+    /// Creates a new `FairRoller`. The commitment should be shared with the player before they choose the client seed.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::fairness::{FairRoller, ServerSeed};
+    ///
+    /// let server_seed = ServerSeed::generate();
+    /// let commitment = server_seed.commitment();
+    /// let roller = FairRoller::new(server_seed, "chosen by the player");
+    /// assert_eq!(roller.commitment(), commitment);
+    /// ```
+    #[must_use]
+    pub fn new(server_seed: ServerSeed, client_seed: &str) -> Self {
+        FairRoller {
+            server_seed,
+            client_seed: client_seed.to_string(),
+            nonce: 0,
+        }
+    }
+
+    /// The commitment to the server seed.
+    #[must_use]
+    pub fn commitment(&self) -> String {
+        self.server_seed.commitment()
+    }
+
+    /// The client seed.
+    #[must_use]
+    pub fn client_seed(&self) -> &str {
+        &self.client_seed
+    }
+
+    /// Rolls the dice with the next nonce, starting from 1.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::str::FromStr;
+    /// use dnd_dice_roller::fairness::{FairRoller, ServerSeed};
+    /// use dnd_dice_roller::roll::Roll;
+    /// # use dnd_dice_roller::error::DiceError;
+    ///
+    /// let mut roller = FairRoller::new(ServerSeed::generate(), "player");
+    /// let record = roller.roll(&Roll::from_str("3d6")?);
+    /// assert_eq!(record.nonce, 1);
+    /// # Ok::<(), DiceError>(())
+    /// ```
+    pub fn roll(&mut self, roll: &Roll) -> FairRollRecord {
+        self.nonce += 1;
+        FairRollRecord {
+            nonce: self.nonce,
+            results: roll.roll_from_rng(rng(&self.server_seed, &self.client_seed, self.nonce)),
+        }
+    }
+
+    /// Ends use of the server seed and reveals it, so that past rolls can be verified.
+    #[must_use]
+    pub fn reveal(self) -> ServerSeed {
+        self.server_seed
+    }
+}
+
+/// Verifies a past roll by checking the revealed server seed matches the commitment and recomputing the results.
+///
+/// # Examples
+/// ```
+/// use std::str::FromStr;
+/// use dnd_dice_roller::fairness::{verify, FairRoller, ServerSeed};
+/// use dnd_dice_roller::roll::Roll;
+/// # use dnd_dice_roller::error::DiceError;
+///
+/// let roll = Roll::from_str("d20 + 3")?;
+/// let mut roller = FairRoller::new(ServerSeed::generate(), "player");
+/// let commitment = roller.commitment();
+/// let record = roller.roll(&roll);
+///
+/// let server_seed = roller.reveal();
+/// verify(&commitment, &server_seed, "player", &roll, &record)?;
+/// # Ok::<(), DiceError>(())
+/// ```
+/// # Errors
+/// Errors with `DiceError::CommitmentMismatch` if the server seed does not hash to the commitment,
+/// or `DiceError::ResultMismatch` if the seeds do not reproduce the results.
+pub fn verify(
+    commitment: &str,
+    server_seed: &ServerSeed,
+    client_seed: &str,
+    roll: &Roll,
+    record: &FairRollRecord,
+) -> Result<(), DiceError> {
+    if !server_seed.commitment().eq_ignore_ascii_case(commitment) {
+        return Err(DiceError::CommitmentMismatch(commitment.to_string()));
+    }
+    let results = roll.roll_from_rng(rng(server_seed, client_seed, record.nonce));
+    if results != record.results {
+        return Err(DiceError::ResultMismatch(record.nonce));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::str::FromStr;
+
+    const SERVER_SEED: &str = "000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f";
+
+    fn server_seed() -> ServerSeed {
+        ServerSeed::from_hex(SERVER_SEED).expect("Valid server seed")
+    }
+
+    #[test]
+    fn hex_round_trips() {
+        assert_eq!(server_seed().to_hex(), SERVER_SEED);
+    }
+
+    #[test]
+    fn commitment_is_sha256_of_the_seed() {
+        assert_eq!(
+            server_seed().commitment(),
+            "630dcd2966c4336691125448bbb25b4ff412a49c732db2c8abc1b8581bd710dd"
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_server_seeds() {
+        assert!(ServerSeed::from_hex("00").is_err());
+        assert!(ServerSeed::from_hex(&"zz".repeat(32)).is_err());
+        assert!(ServerSeed::from_hex(&"é".repeat(32)).is_err());
+    }
+
+    #[test]
+    fn debug_does_not_reveal_the_seed() {
+        assert!(!format!("{:?}", server_seed()).contains(SERVER_SEED));
+    }
+
+    #[test]
+    fn rng_depends_on_every_input() {
+        let roll = Roll::from_str("10d20").expect("Valid roll");
+        let base = roll.roll_from_rng(rng(&server_seed(), "client", 1));
+        assert_eq!(base, roll.roll_from_rng(rng(&server_seed(), "client", 1)));
+        assert_ne!(base, roll.roll_from_rng(rng(&server_seed(), "client", 2)));
+        assert_ne!(base, roll.roll_from_rng(rng(&server_seed(), "other", 1)));
+        assert_ne!(
+            base,
+            roll.roll_from_rng(rng(&ServerSeed([1; 32]), "client", 1))
+        );
+    }
+
+    #[test]
+    fn verifies_past_rolls() {
+        let roll = Roll::from_str("4d6, d20").expect("Valid roll");
+        let mut roller = FairRoller::new(server_seed(), "client");
+        let commitment = roller.commitment();
+        let records: Vec<FairRollRecord> = (0..3).map(|_| roller.roll(&roll)).collect();
+        let revealed = roller.reveal();
+        for record in &records {
+            assert!(verify(&commitment, &revealed, "client", &roll, record).is_ok());
+        }
+    }
+
+    #[test]
+    fn detects_tampering() {
+        let roll = Roll::from_str("d20").expect("Valid roll");
+        let mut roller = FairRoller::new(server_seed(), "client");
+        let commitment = roller.commitment();
+        let record = roller.roll(&roll);
+        let revealed = roller.reveal();
+
+        let other_seed = ServerSeed([7; 32]);
+        assert!(matches!(
+            verify(&commitment, &other_seed, "client", &roll, &record),
+            Err(DiceError::CommitmentMismatch(_))
+        ));
+        assert!(matches!(
+            verify(&commitment, &revealed, "other", &roll, &record),
+            Err(DiceError::ResultMismatch(1))
+        ));
+    }
+}
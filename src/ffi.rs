@@ -0,0 +1,463 @@
+use crate::{
+    dice_result::{DiceSetResults, RollResult},
+    error::DiceError,
+    roll::Roll,
+};
+
+use rand::SeedableRng;
+use std::cell::RefCell;
+use std::ffi::{CStr, CString};
+use std::os::raw::c_char;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::str::FromStr;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Represents the status returned by every C API function. Anything other than `Ok` has a message from `dice_last_error_message`.
+#[repr(C)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DiceStatus {
+    /// The call succeeded.
+    Ok = 0,
+    /// A pointer passed in was null.
+    NullPointer = 1,
+    /// The dice input string was not valid UTF-8.
+    InvalidUtf8 = 2,
+    /// The dice input string is in the wrong format.
+    ParseError = 3,
+    /// A dice set or dice index was past the end of the results.
+    OutOfRange = 4,
+    /// Any other `DiceError`.
+    Error = 5,
+}
+
+impl From<&DiceError> for DiceStatus {
+    fn from(error: &DiceError) -> Self {
+        match error {
+            DiceError::ParseError(_) => DiceStatus::ParseError,
+            _ => DiceStatus::Error,
+        }
+    }
+}
+
+/// Represents a parsed `Roll` owned by C code, freed with `dice_roll_free`.
+pub struct DiceRoll(Roll);
+
+/// Represents the results of rolling a `DiceRoll`, one `DiceSetResults` per comma separated group, freed with `dice_results_free`.
+pub struct DiceResults(Vec<DiceSetResults>);
+
+fn fail(status: DiceStatus, message: &str) -> DiceStatus {
+    // Messages never contain a nul byte, but an empty message is better than losing the status
+    let message = CString::new(message).unwrap_or_default();
+    LAST_ERROR.with(|last_error| *last_error.borrow_mut() = Some(message));
+    status
+}
+
+/// Runs the body of a C API function, turning a panic into `DiceStatus::Error` rather than unwinding into C.
+fn guard(body: impl FnOnce() -> Result<(), DiceStatus>) -> DiceStatus {
+    match panic::catch_unwind(AssertUnwindSafe(body)) {
+        Ok(Ok(())) => DiceStatus::Ok,
+        Ok(Err(status)) => status,
+        Err(_) => fail(DiceStatus::Error, "An unexpected internal error occurred"),
+    }
+}
+
+unsafe fn reference<'a, T>(pointer: *const T) -> Result<&'a T, DiceStatus> {
+    pointer
+        .as_ref()
+        .ok_or_else(|| fail(DiceStatus::NullPointer, "A required pointer was null"))
+}
+
+unsafe fn write<T>(pointer: *mut T, value: T) -> Result<(), DiceStatus> {
+    if pointer.is_null() {
+        return Err(fail(DiceStatus::NullPointer, "A required pointer was null"));
+    }
+    pointer.write(value);
+    Ok(())
+}
+
+/// Hands ownership of a value to C, checking the pointer before allocating so nothing is leaked when it is null.
+unsafe fn write_box<T>(pointer: *mut *mut T, value: T) -> Result<(), DiceStatus> {
+    if pointer.is_null() {
+        return Err(fail(DiceStatus::NullPointer, "A required pointer was null"));
+    }
+    pointer.write(Box::into_raw(Box::new(value)));
+    Ok(())
+}
+
+unsafe fn dice_set<'a>(
+    results: *const DiceResults,
+    set: usize,
+) -> Result<&'a DiceSetResults, DiceStatus> {
+    reference(results)?.0.get(set).ok_or_else(|| {
+        fail(
+            DiceStatus::OutOfRange,
+            &format!("There is no dice set {set} in the results"),
+        )
+    })
+}
+
+unsafe fn dice<'a>(
+    results: *const DiceResults,
+    set: usize,
+    dice: usize,
+) -> Result<&'a RollResult, DiceStatus> {
+    dice_set(results, set)?
+        .dice_results
+        .get(dice)
+        .ok_or_else(|| {
+            fail(
+                DiceStatus::OutOfRange,
+                &format!("There is no dice {dice} in dice set {set}"),
+            )
+        })
+}
+
+unsafe fn copy_faces(
+    source: &[u32],
+    faces: *mut u32,
+    capacity: usize,
+    length: *mut usize,
+) -> Result<(), DiceStatus> {
+    if !faces.is_null() {
+        let copied = source.len().min(capacity);
+        ptr::copy_nonoverlapping(source.as_ptr(), faces, copied);
+    }
+    write(length, source.len())
+}
+
+/// The message for the last failed call on this thread, or null if no call has failed.
+/// The message is owned by the library and is valid until the next failed call on this thread.
+#[no_mangle]
+pub extern "C" fn dice_last_error_message() -> *const c_char {
+    panic::catch_unwind(|| {
+        LAST_ERROR.with(|last_error| {
+            last_error
+                .borrow()
+                .as_ref()
+                .map_or(ptr::null(), |message| message.as_ptr())
+        })
+    })
+    .unwrap_or(ptr::null())
+}
+
+/// Parses a nul terminated dice input string e.g. `2d20 + 1 a, 3d6` into a new `DiceRoll` written to `roll`.
+///
+/// # Safety
+/// `input` must be null or a valid nul terminated string, and `roll` must be null or valid to write to.
+#[no_mangle]
+pub unsafe extern "C" fn dice_roll_parse(
+    input: *const c_char,
+    roll: *mut *mut DiceRoll,
+) -> DiceStatus {
+    guard(|| {
+        if input.is_null() {
+            return Err(fail(
+                DiceStatus::NullPointer,
+                "The dice input string was null",
+            ));
+        }
+        let input = CStr::from_ptr(input).to_str().map_err(|_| {
+            fail(
+                DiceStatus::InvalidUtf8,
+                "The dice input string was not valid UTF-8",
+            )
+        })?;
+        let parsed =
+            Roll::from_str(input).map_err(|error| fail((&error).into(), &error.to_string()))?;
+        write_box(roll, DiceRoll(parsed))
+    })
+}
+
+/// Frees a `DiceRoll` created by `dice_roll_parse`. Does nothing when given null.
+///
+/// # Safety
+/// `roll` must be null or a `DiceRoll` from `dice_roll_parse` which has not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn dice_roll_free(roll: *mut DiceRoll) {
+    guard(|| {
+        if !roll.is_null() {
+            drop(Box::from_raw(roll));
+        }
+        Ok(())
+    });
+}
+
+/// Rolls a `DiceRoll` with a `Pcg64Mcg` RNG created from the seed, writing new `DiceResults` to `results`.
+///
+/// # Safety
+/// `roll` must be null or a valid `DiceRoll`, and `results` must be null or valid to write to.
+#[no_mangle]
+pub unsafe extern "C" fn dice_roll_roll_seeded(
+    roll: *const DiceRoll,
+    seed: u64,
+    results: *mut *mut DiceResults,
+) -> DiceStatus {
+    guard(|| {
+        let rolled = reference(roll)?
+            .0
+            .roll_from_rng(rand_pcg::Pcg64Mcg::seed_from_u64(seed));
+        write_box(results, DiceResults(rolled))
+    })
+}
+
+/// Rolls a `DiceRoll` with the OS RNG, writing new `DiceResults` to `results`.
+///
+/// # Safety
+/// `roll` must be null or a valid `DiceRoll`, and `results` must be null or valid to write to.
+#[no_mangle]
+pub unsafe extern "C" fn dice_roll_roll(
+    roll: *const DiceRoll,
+    results: *mut *mut DiceResults,
+) -> DiceStatus {
+    guard(|| {
+        let rolled = reference(roll)?.0.roll();
+        write_box(results, DiceResults(rolled))
+    })
+}
+
+/// Frees `DiceResults` created by rolling. Does nothing when given null.
+///
+/// # Safety
+/// `results` must be null or `DiceResults` from `dice_roll_roll` or `dice_roll_roll_seeded` which have not been freed.
+#[no_mangle]
+pub unsafe extern "C" fn dice_results_free(results: *mut DiceResults) {
+    guard(|| {
+        if !results.is_null() {
+            drop(Box::from_raw(results));
+        }
+        Ok(())
+    });
+}
+
+/// Writes the number of dice sets, one per comma separated group, to `count`.
+///
+/// # Safety
+/// `results` must be null or valid `DiceResults`, and `count` must be null or valid to write to.
+#[no_mangle]
+pub unsafe extern "C" fn dice_results_set_count(
+    results: *const DiceResults,
+    count: *mut usize,
+) -> DiceStatus {
+    guard(|| reference(results).and_then(|results| write(count, results.0.len())))
+}
+
+/// Writes the total of a dice set to `total`.
+///
+/// # Safety
+/// `results` must be null or valid `DiceResults`, and `total` must be null or valid to write to.
+#[no_mangle]
+pub unsafe extern "C" fn dice_results_final_result(
+    results: *const DiceResults,
+    set: usize,
+    total: *mut i32,
+) -> DiceStatus {
+    guard(|| dice_set(results, set).and_then(|dice_set| write(total, dice_set.final_result)))
+}
+
+/// Writes the number of dice in a dice set, one per term e.g. two for `d20 + d4`, to `count`.
+///
+/// # Safety
+/// `results` must be null or valid `DiceResults`, and `count` must be null or valid to write to.
+#[no_mangle]
+pub unsafe extern "C" fn dice_results_dice_count(
+    results: *const DiceResults,
+    set: usize,
+    count: *mut usize,
+) -> DiceStatus {
+    guard(|| dice_set(results, set).and_then(|dice_set| write(count, dice_set.dice_results.len())))
+}
+
+/// Writes the result of a single dice in a dice set, including its modifier, to `result`.
+///
+/// # Safety
+/// `results` must be null or valid `DiceResults`, and `result` must be null or valid to write to.
+#[no_mangle]
+pub unsafe extern "C" fn dice_results_dice_result(
+    results: *const DiceResults,
+    set: usize,
+    dice_index: usize,
+    result: *mut i32,
+) -> DiceStatus {
+    guard(|| dice(results, set, dice_index).and_then(|dice| write(result, dice.result)))
+}
+
+/// Copies up to `capacity` faces of the first roll of a dice into `faces`, and writes the number of faces rolled to `length`.
+/// `faces` may be null to find the length alone.
+///
+/// # Safety
+/// `results` must be null or valid `DiceResults`, `faces` must be null or valid to write `capacity` faces to,
+/// and `length` must be null or valid to write to.
+#[no_mangle]
+pub unsafe extern "C" fn dice_results_first_roll(
+    results: *const DiceResults,
+    set: usize,
+    dice_index: usize,
+    faces: *mut u32,
+    capacity: usize,
+    length: *mut usize,
+) -> DiceStatus {
+    guard(|| {
+        dice(results, set, dice_index)
+            .and_then(|dice| copy_faces(&dice.first_roll, faces, capacity, length))
+    })
+}
+
+/// Copies up to `capacity` faces of the second roll of a dice with advantage or disadvantage into `faces`,
+/// and writes the number of faces rolled to `length`. The length is zero for a dice without a second roll.
+///
+/// # Safety
+/// `results` must be null or valid `DiceResults`, `faces` must be null or valid to write `capacity` faces to,
+/// and `length` must be null or valid to write to.
+#[no_mangle]
+pub unsafe extern "C" fn dice_results_second_roll(
+    results: *const DiceResults,
+    set: usize,
+    dice_index: usize,
+    faces: *mut u32,
+    capacity: usize,
+    length: *mut usize,
+) -> DiceStatus {
+    guard(|| {
+        dice(results, set, dice_index).and_then(|dice| {
+            let second_roll = dice.second_roll.as_deref().unwrap_or_default();
+            copy_faces(second_roll, faces, capacity, length)
+        })
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(input: &str) -> Result<*mut DiceRoll, DiceStatus> {
+        let input = CString::new(input).expect("No nul bytes");
+        let mut roll = ptr::null_mut();
+        match unsafe { dice_roll_parse(input.as_ptr(), ptr::addr_of_mut!(roll)) } {
+            DiceStatus::Ok => Ok(roll),
+            error => Err(error),
+        }
+    }
+
+    fn last_error() -> String {
+        unsafe { CStr::from_ptr(dice_last_error_message()) }
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn rolls_seeded_dice() {
+        let roll = parse("3d6 + 1, d20 a").expect("Valid roll");
+        let mut results = ptr::null_mut();
+        let mut total = 0;
+        let mut faces = [0; 4];
+        let mut length = 0;
+        unsafe {
+            assert_eq!(
+                dice_roll_roll_seeded(roll, 42, ptr::addr_of_mut!(results)),
+                DiceStatus::Ok
+            );
+            assert_eq!(
+                dice_results_final_result(results, 0, ptr::addr_of_mut!(total)),
+                DiceStatus::Ok
+            );
+            assert_eq!(
+                dice_results_first_roll(
+                    results,
+                    0,
+                    0,
+                    faces.as_mut_ptr(),
+                    4,
+                    ptr::addr_of_mut!(length)
+                ),
+                DiceStatus::Ok
+            );
+            assert_eq!(
+                dice_results_second_roll(
+                    results,
+                    1,
+                    0,
+                    ptr::null_mut(),
+                    0,
+                    ptr::addr_of_mut!(length)
+                ),
+                DiceStatus::Ok
+            );
+            dice_results_free(results);
+            dice_roll_free(roll);
+        }
+        assert_eq!(total, 14);
+        assert_eq!(faces[..3], [2, 6, 5]);
+        assert_eq!(length, 1);
+    }
+
+    #[test]
+    fn reports_parse_errors() {
+        assert_eq!(parse("2d").err(), Some(DiceStatus::ParseError));
+        assert!(last_error().starts_with("Error parsing input"));
+    }
+
+    #[test]
+    fn reports_null_pointers_and_out_of_range_indices() {
+        let mut count = 0;
+        unsafe {
+            assert_eq!(
+                dice_results_set_count(ptr::null(), ptr::addr_of_mut!(count)),
+                DiceStatus::NullPointer
+            );
+            assert_eq!(
+                dice_roll_parse(ptr::null(), ptr::null_mut()),
+                DiceStatus::NullPointer
+            );
+        }
+
+        let roll = parse("d6").expect("Valid roll");
+        let mut results = ptr::null_mut();
+        unsafe {
+            assert_eq!(
+                dice_roll_roll(roll, ptr::addr_of_mut!(results)),
+                DiceStatus::Ok
+            );
+            assert_eq!(
+                dice_results_dice_count(results, 1, ptr::addr_of_mut!(count)),
+                DiceStatus::OutOfRange
+            );
+            assert_eq!(last_error(), "There is no dice set 1 in the results");
+            dice_results_free(results);
+            dice_roll_free(roll);
+        }
+    }
+
+    #[test]
+    fn reports_null_out_pointers_for_valid_input() {
+        let input = CString::new("d6").expect("No nul bytes");
+        let roll = parse("d6").expect("Valid roll");
+        unsafe {
+            assert_eq!(
+                dice_roll_parse(input.as_ptr(), ptr::null_mut()),
+                DiceStatus::NullPointer
+            );
+            assert_eq!(
+                dice_roll_roll_seeded(roll, 42, ptr::null_mut()),
+                DiceStatus::NullPointer
+            );
+            assert_eq!(
+                dice_roll_roll(roll, ptr::null_mut()),
+                DiceStatus::NullPointer
+            );
+            dice_roll_free(roll);
+        }
+    }
+
+    #[test]
+    fn reports_panics_as_errors() {
+        assert_eq!(
+            guard(|| panic!("Panics must not unwind into C")),
+            DiceStatus::Error
+        );
+        assert_eq!(last_error(), "An unexpected internal error occurred");
+    }
+}
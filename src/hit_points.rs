@@ -0,0 +1,319 @@
+use crate::{
+    dice::{Dice, Operation, RollType},
+    dice_result::RollResult,
+    distribution::Distribution,
+};
+
+use rand::Rng;
+
+/// Represents a single level gained by a character. e.g. A level of Fighter with a +2 Constitution modifier.
+#[derive(Clone, PartialEq, Debug)]
+pub struct LevelUp {
+    /// How many sides the hit die of the class the level was taken in has. e.g. 10 for a Fighter.
+    pub hit_die: u32,
+    /// The character's Constitution modifier at the time the level was gained.
+    pub constitution_modifier: i32,
+}
+
+impl LevelUp {
+    /// Creates a new `LevelUp`
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::hit_points::LevelUp;
+    /// // A level of Wizard with a +1 Constitution modifier
+    /// let level = LevelUp::new(6, 1);
+    /// ```
+    #[must_use]
+    pub fn new(hit_die: u32, constitution_modifier: i32) -> Self {
+        LevelUp {
+            hit_die,
+            constitution_modifier,
+        }
+    }
+
+    /// The fixed number of hit points the hit die grants when not rolling. e.g. 6 for a d10.
+    #[must_use]
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn average_hit_die(&self) -> i32 {
+        (self.hit_die / 2 + 1) as i32
+    }
+}
+
+/// Represents how hit points are gained for levels after the first.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum HitPointMethod {
+    /// The hit die is rolled.
+    Rolled,
+    /// The fixed average of the hit die is taken. e.g. 6 for a d10.
+    Average,
+}
+
+/// Represents the rules used when calculating hit points.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct HitPointOptions {
+    /// How hit points are gained for levels after the first.
+    pub method: HitPointMethod,
+    /// Whether the first level grants the maximum of its hit die rather than following `method`.
+    pub maximum_at_first_level: bool,
+    /// Whether every level grants at least one hit point, even with a negative Constitution modifier.
+    pub minimum_one_per_level: bool,
+}
+
+impl Default for HitPointOptions {
+    fn default() -> Self {
+        HitPointOptions {
+            method: HitPointMethod::Rolled,
+            maximum_at_first_level: true,
+            minimum_one_per_level: true,
+        }
+    }
+}
+
+/// Represents the hit points gained at a single level.
+#[derive(PartialEq, Debug)]
+pub struct LevelHitPoints {
+    /// The character level this entry is for, starting at 1.
+    pub level: u32,
+    /// How many sides the hit die used for this level has.
+    pub hit_die: u32,
+    /// The roll of the hit die (including the Constitution modifier). Only present when the hit die was rolled.
+    pub roll: Option<RollResult>,
+    /// The hit points gained at this level.
+    pub hit_points: i32,
+}
+
+/// Represents the result of rolling hit points for a character.
+#[derive(PartialEq, Debug)]
+pub struct HitPointResults {
+    /// The hit points gained at each level, in the order they were taken.
+    pub levels: Vec<LevelHitPoints>,
+    /// The character's maximum hit points.
+    pub total: i32,
+}
+
+/// Represents a character's level history, from which hit points can be rolled.
+#[derive(Debug)]
+pub struct HitPoints {
+    levels: Vec<LevelUp>,
+    options: HitPointOptions,
+}
+
+impl HitPoints {
+    /// Creates a new `HitPoints` from the levels a character has taken, in the order they were taken.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::hit_points::{HitPoints, HitPointOptions, LevelUp};
+    /// // A Fighter 2 / Wizard 1 with a +2 Constitution modifier
+    /// let levels = vec![LevelUp::new(10, 2), LevelUp::new(10, 2), LevelUp::new(6, 2)];
+    /// let hit_points = HitPoints::new(levels, HitPointOptions::default());
+    /// ```
+    #[must_use]
+    pub fn new(levels: Vec<LevelUp>, options: HitPointOptions) -> Self {
+        HitPoints { levels, options }
+    }
+
+    /// Rolls hit points for each level and produces a `HitPointResults`. Using underlying OS RNG for the dice roll.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::hit_points::{HitPoints, HitPointOptions, LevelUp};
+    ///
+    /// let levels = vec![LevelUp::new(8, 1), LevelUp::new(8, 1)];
+    /// let hit_points = HitPoints::new(levels, HitPointOptions::default());
+    /// let result = hit_points.roll_hit_points();
+    /// assert!(result.total >= 11 && result.total <= 18);
+    /// ```
+    #[must_use]
+    pub fn roll_hit_points(&self) -> HitPointResults {
+        let mut rng = rand::thread_rng();
+        self.roll_hit_points_from_rng(&mut rng)
+    }
+
+    /// Rolls hit points for each level and produces a `HitPointResults`. Uses a source of RNG passed in. Useful for testing.
+    ///
+    /// # Examples
+    /// ```
+    /// use rand::SeedableRng;
+    /// use dnd_dice_roller::hit_points::{HitPoints, HitPointOptions, LevelUp};
+    ///
+    /// let rng = rand_pcg::Pcg64Mcg::seed_from_u64(42);
+    /// let levels = vec![LevelUp::new(6, 0), LevelUp::new(6, 0)];
+    /// let hit_points = HitPoints::new(levels, HitPointOptions::default());
+    /// let result = hit_points.roll_hit_points_from_rng(rng);
+    /// assert_eq!(result.total, 8);
+    /// ```
+    pub fn roll_hit_points_from_rng<R: Rng + Sized>(&self, mut rng: R) -> HitPointResults {
+        let levels: Vec<LevelHitPoints> = self
+            .levels
+            .iter()
+            .zip(1..)
+            .map(|(level_up, level)| {
+                let (roll, hit_points) = if self.is_fixed(level) {
+                    (None, self.fixed_hit_points(level_up, level))
+                } else {
+                    let roll = Self::hit_die(level_up).roll_dice_from_rng(&mut rng);
+                    let hit_points = roll.result;
+                    (Some(roll), hit_points)
+                };
+                LevelHitPoints {
+                    level,
+                    hit_die: level_up.hit_die,
+                    roll,
+                    hit_points: self.apply_minimum(hit_points),
+                }
+            })
+            .collect();
+        let total = levels.iter().map(|level| level.hit_points).sum();
+
+        HitPointResults { levels, total }
+    }
+
+    /// Calculates the exact probability of each maximum hit point total the level history can produce.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::hit_points::{HitPoints, HitPointOptions, LevelUp};
+    ///
+    /// let levels = vec![LevelUp::new(12, 3), LevelUp::new(12, 3)];
+    /// let hit_points = HitPoints::new(levels, HitPointOptions::default());
+    /// let distribution = hit_points.distribution();
+    /// assert_eq!(distribution.min(), Some(19));
+    /// assert_eq!(distribution.max(), Some(30));
+    /// ```
+    #[must_use]
+    pub fn distribution(&self) -> Distribution {
+        self.levels
+            .iter()
+            .zip(1..)
+            .map(|(level_up, level)| {
+                let distribution = if self.is_fixed(level) {
+                    Distribution::constant(self.fixed_hit_points(level_up, level))
+                } else {
+                    Self::hit_die(level_up).distribution()
+                };
+                distribution.map(|hit_points| self.apply_minimum(hit_points))
+            })
+            .fold(Distribution::constant(0), |total, level| total.add(&level))
+    }
+
+    fn hit_die(level_up: &LevelUp) -> Dice {
+        Dice::new(
+            1,
+            level_up.hit_die,
+            Some(level_up.constitution_modifier),
+            RollType::Regular,
+            Operation::Addition,
+        )
+    }
+
+    fn is_fixed(&self, level: u32) -> bool {
+        (level == 1 && self.options.maximum_at_first_level)
+            || self.options.method == HitPointMethod::Average
+    }
+
+    #[allow(clippy::cast_possible_wrap)]
+    fn fixed_hit_points(&self, level_up: &LevelUp, level: u32) -> i32 {
+        let hit_die = if level == 1 && self.options.maximum_at_first_level {
+            level_up.hit_die as i32
+        } else {
+            level_up.average_hit_die()
+        };
+        hit_die + level_up.constitution_modifier
+    }
+
+    fn apply_minimum(&self, hit_points: i32) -> i32 {
+        if self.options.minimum_one_per_level {
+            hit_points.max(1)
+        } else {
+            hit_points
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use rand::SeedableRng;
+
+    const SEED: u64 = 42;
+
+    #[test]
+    fn first_level_is_maximum_and_later_levels_are_rolled() {
+        let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let levels = vec![LevelUp::new(6, 1), LevelUp::new(6, 1)];
+        let hit_points = HitPoints::new(levels, HitPointOptions::default());
+        let result = hit_points.roll_hit_points_from_rng(rng);
+        let expected = vec![
+            LevelHitPoints {
+                level: 1,
+                hit_die: 6,
+                roll: None,
+                hit_points: 7,
+            },
+            LevelHitPoints {
+                level: 2,
+                hit_die: 6,
+                roll: Some(RollResult::new(vec![2], None, 3)),
+                hit_points: 3,
+            },
+        ];
+        assert_eq!(result.levels, expected);
+        assert_eq!(result.total, 10);
+    }
+
+    #[test]
+    fn average_method_uses_fixed_values() {
+        let options = HitPointOptions {
+            method: HitPointMethod::Average,
+            ..HitPointOptions::default()
+        };
+        let levels = vec![LevelUp::new(10, 2), LevelUp::new(10, 2), LevelUp::new(8, 2)];
+        let hit_points = HitPoints::new(levels, options);
+        let result = hit_points.roll_hit_points();
+        assert_eq!(result.total, 12 + 8 + 7);
+        assert!(result.levels.iter().all(|level| level.roll.is_none()));
+        assert!((hit_points.distribution().probability(27) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn minimum_one_hit_point_per_level() {
+        let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let levels = vec![LevelUp::new(6, -3), LevelUp::new(6, -3)];
+        let hit_points = HitPoints::new(levels, HitPointOptions::default());
+        let result = hit_points.roll_hit_points_from_rng(rng);
+        assert_eq!(
+            result.levels[1].roll,
+            Some(RollResult::new(vec![2], None, -1))
+        );
+        assert_eq!(result.levels[1].hit_points, 1);
+        assert_eq!(result.total, 4);
+    }
+
+    #[test]
+    fn without_minimum_hit_points_can_be_lost() {
+        let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let options = HitPointOptions {
+            minimum_one_per_level: false,
+            ..HitPointOptions::default()
+        };
+        let levels = vec![LevelUp::new(6, -3), LevelUp::new(6, -3)];
+        let hit_points = HitPoints::new(levels, options);
+        let result = hit_points.roll_hit_points_from_rng(rng);
+        assert_eq!(result.levels[1].hit_points, -1);
+        assert_eq!(result.total, 2);
+    }
+
+    #[test]
+    fn distribution_applies_minimum_per_level() {
+        let levels = vec![LevelUp::new(6, -3), LevelUp::new(6, -3)];
+        let hit_points = HitPoints::new(levels, HitPointOptions::default());
+        let distribution = hit_points.distribution();
+        // The second level grants 1 hit point on a roll of 1 to 4
+        assert!((distribution.probability(4) - 4.0 / 6.0).abs() < 1e-9);
+        assert!((distribution.probability(6) - 1.0 / 6.0).abs() < 1e-9);
+        assert_eq!(distribution.min(), Some(4));
+    }
+}
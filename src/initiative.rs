@@ -0,0 +1,447 @@
+use crate::{
+    dice::{Dice, Operation, RollType},
+    dice_result::RollResult,
+};
+
+use rand::Rng;
+
+use std::cmp::Ordering;
+
+/// Represents a creature taking part in an encounter.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Combatant {
+    /// The name of the creature. e.g. `Goblin 1`
+    pub name: String,
+    /// The creature's Dexterity modifier, which is added to its initiative roll and breaks ties.
+    pub dexterity_modifier: i32,
+    /// Whether the creature rolls initiative with advantage or disadvantage.
+    pub roll_type: RollType,
+    /// An optional group name. When grouping is enabled, all combatants in a group share a single initiative roll.
+    pub group: Option<String>,
+}
+
+impl Combatant {
+    /// Creates a new `Combatant`
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::dice::RollType;
+    /// use dnd_dice_roller::initiative::Combatant;
+    ///
+    /// let rogue = Combatant::new("Rogue", 4, RollType::Advantage, None);
+    /// let goblin = Combatant::new("Goblin 1", 2, RollType::Regular, Some("Goblins"));
+    /// ```
+    #[must_use]
+    pub fn new(
+        name: &str,
+        dexterity_modifier: i32,
+        roll_type: RollType,
+        group: Option<&str>,
+    ) -> Self {
+        Combatant {
+            name: name.to_string(),
+            dexterity_modifier,
+            roll_type,
+            group: group.map(str::to_string),
+        }
+    }
+}
+
+/// Represents a single place in the initiative order, held by one combatant or a group of combatants.
+#[derive(PartialEq, Debug)]
+pub struct InitiativeEntry {
+    /// The names of the combatants acting on this turn.
+    pub names: Vec<String>,
+    /// The group the combatants belong to, if they were rolled as a group.
+    pub group: Option<String>,
+    /// The Dexterity modifier used for the roll and the first tie breaker.
+    pub dexterity_modifier: i32,
+    /// The initiative roll.
+    pub roll: RollResult,
+    /// The d20 roll-offs made to break ties against combatants with the same initiative and Dexterity, in the order they were rolled.
+    pub tie_breakers: Vec<u32>,
+}
+
+impl InitiativeEntry {
+    /// The initiative total for this entry.
+    #[must_use]
+    pub fn initiative(&self) -> i32 {
+        self.roll.result
+    }
+
+    fn compare(&self, other: &Self) -> Ordering {
+        other
+            .initiative()
+            .cmp(&self.initiative())
+            .then_with(|| other.dexterity_modifier.cmp(&self.dexterity_modifier))
+            .then_with(|| other.tie_breakers.cmp(&self.tie_breakers))
+    }
+
+    fn ties_with(&self, other: &Self) -> bool {
+        self.compare(other) == Ordering::Equal
+    }
+}
+
+/// Represents the combatants in an encounter, for whom initiative can be rolled.
+#[derive(Debug)]
+pub struct Initiative {
+    combatants: Vec<Combatant>,
+    group_combatants: bool,
+}
+
+impl Initiative {
+    /// Creates a new `Initiative`. When `group_combatants` is true, combatants sharing a `group` roll initiative once and act together.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::dice::RollType;
+    /// use dnd_dice_roller::initiative::{Combatant, Initiative};
+    ///
+    /// let combatants = vec![
+    ///     Combatant::new("Fighter", 1, RollType::Regular, None),
+    ///     Combatant::new("Goblin 1", 2, RollType::Regular, Some("Goblins")),
+    ///     Combatant::new("Goblin 2", 2, RollType::Regular, Some("Goblins")),
+    /// ];
+    /// let initiative = Initiative::new(combatants, true);
+    /// ```
+    #[must_use]
+    pub fn new(combatants: Vec<Combatant>, group_combatants: bool) -> Self {
+        Initiative {
+            combatants,
+            group_combatants,
+        }
+    }
+
+    /// Rolls initiative for every combatant and produces an `InitiativeOrder`. Using underlying OS RNG for the dice roll.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::dice::RollType;
+    /// use dnd_dice_roller::initiative::{Combatant, Initiative};
+    ///
+    /// let combatants = vec![
+    ///     Combatant::new("Fighter", 1, RollType::Regular, None),
+    ///     Combatant::new("Wizard", 2, RollType::Regular, None),
+    /// ];
+    /// let order = Initiative::new(combatants, false).roll_initiative();
+    /// assert_eq!(order.entries().len(), 2);
+    /// ```
+    #[must_use]
+    pub fn roll_initiative(&self) -> InitiativeOrder {
+        let mut rng = rand::thread_rng();
+        self.roll_initiative_from_rng(&mut rng)
+    }
+
+    /// Rolls initiative for every combatant and produces an `InitiativeOrder`. Uses a source of RNG passed in. Useful for testing.
+    ///
+    /// Combatants are ordered by initiative, then by Dexterity modifier, then by d20 roll-offs until every tie is broken.
+    ///
+    /// # Examples
+    /// ```
+    /// use rand::SeedableRng;
+    /// use dnd_dice_roller::dice::RollType;
+    /// use dnd_dice_roller::initiative::{Combatant, Initiative};
+    ///
+    /// let rng = rand_pcg::Pcg64Mcg::seed_from_u64(42);
+    /// let combatants = vec![
+    ///     Combatant::new("Fighter", 1, RollType::Regular, None),
+    ///     Combatant::new("Wizard", 2, RollType::Regular, None),
+    /// ];
+    /// let order = Initiative::new(combatants, false).roll_initiative_from_rng(rng);
+    /// assert_eq!(order.current().unwrap().names, vec!["Wizard"]);
+    /// ```
+    pub fn roll_initiative_from_rng<R: Rng + Sized>(&self, mut rng: R) -> InitiativeOrder {
+        let mut entries: Vec<InitiativeEntry> = Vec::with_capacity(self.combatants.len());
+        for combatant in &self.combatants {
+            let group = combatant.group.as_ref().filter(|_| self.group_combatants);
+            if let Some(existing) = entries
+                .iter_mut()
+                .find(|entry| group.is_some() && entry.group.as_ref() == group)
+            {
+                existing.names.push(combatant.name.clone());
+                continue;
+            }
+
+            let dice = Dice::new(
+                1,
+                20,
+                Some(combatant.dexterity_modifier),
+                combatant.roll_type,
+                Operation::Addition,
+            );
+            entries.push(InitiativeEntry {
+                names: vec![combatant.name.clone()],
+                group: group.cloned(),
+                dexterity_modifier: combatant.dexterity_modifier,
+                roll: dice.roll_dice_from_rng(&mut rng),
+                tie_breakers: Vec::new(),
+            });
+        }
+
+        Self::break_ties(&mut entries, &mut rng);
+        entries.sort_by(InitiativeEntry::compare);
+
+        InitiativeOrder {
+            entries,
+            round: 1,
+            turn: 0,
+        }
+    }
+
+    fn break_ties<R: Rng + Sized>(entries: &mut [InitiativeEntry], rng: &mut R) {
+        loop {
+            let tied: Vec<usize> = (0..entries.len())
+                .filter(|&index| {
+                    entries
+                        .iter()
+                        .enumerate()
+                        .any(|(other, entry)| other != index && entry.ties_with(&entries[index]))
+                })
+                .collect();
+            if tied.is_empty() {
+                return;
+            }
+            for index in tied {
+                entries[index].tie_breakers.push(rng.gen_range(1..=20));
+            }
+        }
+    }
+}
+
+/// Represents the order in which combatants act, along with the current round and turn.
+#[derive(PartialEq, Debug)]
+pub struct InitiativeOrder {
+    entries: Vec<InitiativeEntry>,
+    round: u32,
+    turn: usize,
+}
+
+impl InitiativeOrder {
+    /// The entries in the order they act, highest initiative first.
+    #[must_use]
+    pub fn entries(&self) -> &[InitiativeEntry] {
+        &self.entries
+    }
+
+    /// The current round, starting at 1.
+    #[must_use]
+    pub fn round(&self) -> u32 {
+        self.round
+    }
+
+    /// The index into `entries` of the entry whose turn it is.
+    #[must_use]
+    pub fn turn(&self) -> usize {
+        self.turn
+    }
+
+    /// The entry whose turn it is. Only `None` when there are no combatants.
+    #[must_use]
+    pub fn current(&self) -> Option<&InitiativeEntry> {
+        self.entries.get(self.turn)
+    }
+
+    /// Advances to the next turn, starting a new round after the last entry has acted. Returns the entry whose turn it now is.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::dice::RollType;
+    /// use dnd_dice_roller::initiative::{Combatant, Initiative};
+    ///
+    /// let combatants = vec![
+    ///     Combatant::new("Fighter", 1, RollType::Regular, None),
+    ///     Combatant::new("Wizard", 2, RollType::Regular, None),
+    /// ];
+    /// let mut order = Initiative::new(combatants, false).roll_initiative();
+    /// order.advance();
+    /// order.advance();
+    /// assert_eq!(order.round(), 2);
+    /// assert_eq!(order.turn(), 0);
+    /// ```
+    pub fn advance(&mut self) -> Option<&InitiativeEntry> {
+        if self.entries.is_empty() {
+            return None;
+        }
+        self.turn += 1;
+        if self.turn >= self.entries.len() {
+            self.turn = 0;
+            self.round += 1;
+        }
+        self.current()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::scripted_rng::ScriptedRng;
+    use rand::SeedableRng;
+
+    const SEED: u64 = 42;
+
+    fn names(order: &InitiativeOrder) -> Vec<Vec<String>> {
+        order
+            .entries()
+            .iter()
+            .map(|entry| entry.names.clone())
+            .collect()
+    }
+
+    #[test]
+    fn orders_by_initiative_descending() {
+        let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let combatants = vec![
+            Combatant::new("Fighter", 0, RollType::Regular, None),
+            Combatant::new("Wizard", 2, RollType::Regular, None),
+            Combatant::new("Rogue", 4, RollType::Regular, None),
+        ];
+        let order = Initiative::new(combatants, false).roll_initiative_from_rng(rng);
+        // The Rogue's modifier lifts its 3 above the Fighter's 6
+        let rolls: Vec<(Vec<String>, Vec<u32>, i32)> = order
+            .entries()
+            .iter()
+            .map(|entry| {
+                (
+                    entry.names.clone(),
+                    entry.roll.first_roll.clone(),
+                    entry.initiative(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            rolls,
+            vec![
+                (vec!["Wizard".to_string()], vec![16], 18),
+                (vec!["Rogue".to_string()], vec![3], 7),
+                (vec!["Fighter".to_string()], vec![6], 6),
+            ]
+        );
+    }
+
+    #[test]
+    fn scripted_ties_fall_back_to_dexterity_then_roll_offs() {
+        // Everyone totals 15, Slow loses on Dexterity, then Quick and Quicker tie on a 5 before rolling 8 and 17
+        let rng = ScriptedRng::new(20, &[14, 12, 12, 5, 5, 8, 17]);
+        let combatants = vec![
+            Combatant::new("Slow", 1, RollType::Regular, None),
+            Combatant::new("Quick", 3, RollType::Regular, None),
+            Combatant::new("Quicker", 3, RollType::Regular, None),
+        ];
+        let order = Initiative::new(combatants, false).roll_initiative_from_rng(rng);
+        let entries = order.entries();
+        assert_eq!(
+            names(&order),
+            vec![
+                vec!["Quicker".to_string()],
+                vec!["Quick".to_string()],
+                vec!["Slow".to_string()]
+            ]
+        );
+        assert!(entries.iter().all(|entry| entry.initiative() == 15));
+        assert_eq!(entries[0].tie_breakers, vec![5, 17]);
+        assert_eq!(entries[1].tie_breakers, vec![5, 8]);
+        assert!(entries[2].tie_breakers.is_empty());
+    }
+
+    #[test]
+    fn ties_broken_by_dexterity() {
+        let mut entries = vec![
+            InitiativeEntry {
+                names: vec!["Slow".to_string()],
+                group: None,
+                dexterity_modifier: 1,
+                roll: RollResult::new(vec![14], None, 15),
+                tie_breakers: Vec::new(),
+            },
+            InitiativeEntry {
+                names: vec!["Quick".to_string()],
+                group: None,
+                dexterity_modifier: 3,
+                roll: RollResult::new(vec![12], None, 15),
+                tie_breakers: Vec::new(),
+            },
+        ];
+        let mut rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        Initiative::break_ties(&mut entries, &mut rng);
+        entries.sort_by(InitiativeEntry::compare);
+        assert_eq!(entries[0].names, vec!["Quick"]);
+        assert!(entries.iter().all(|entry| entry.tie_breakers.is_empty()));
+    }
+
+    #[test]
+    fn exact_ties_are_rerolled_until_broken() {
+        let tied_entry = |name: &str| InitiativeEntry {
+            names: vec![name.to_string()],
+            group: None,
+            dexterity_modifier: 2,
+            roll: RollResult::new(vec![10], None, 12),
+            tie_breakers: Vec::new(),
+        };
+        let mut entries = vec![tied_entry("A"), tied_entry("B"), tied_entry("C")];
+        let mut rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        Initiative::break_ties(&mut entries, &mut rng);
+        entries.sort_by(InitiativeEntry::compare);
+        assert!(entries.iter().all(|entry| !entry.tie_breakers.is_empty()));
+        assert!(entries[0].tie_breakers > entries[1].tie_breakers);
+        assert!(entries[1].tie_breakers > entries[2].tie_breakers);
+    }
+
+    #[test]
+    fn grouped_combatants_share_a_roll() {
+        let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let combatants = vec![
+            Combatant::new("Goblin 1", 2, RollType::Regular, Some("Goblins")),
+            Combatant::new("Fighter", 1, RollType::Regular, None),
+            Combatant::new("Goblin 2", 2, RollType::Regular, Some("Goblins")),
+        ];
+        let order = Initiative::new(combatants, true).roll_initiative_from_rng(rng);
+        assert_eq!(order.entries().len(), 2);
+        assert!(names(&order).contains(&vec!["Goblin 1".to_string(), "Goblin 2".to_string()]));
+    }
+
+    #[test]
+    fn groups_ignored_when_grouping_disabled() {
+        let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let combatants = vec![
+            Combatant::new("Goblin 1", 2, RollType::Regular, Some("Goblins")),
+            Combatant::new("Goblin 2", 2, RollType::Regular, Some("Goblins")),
+        ];
+        let order = Initiative::new(combatants, false).roll_initiative_from_rng(rng);
+        assert_eq!(order.entries().len(), 2);
+        assert!(order.entries().iter().all(|entry| entry.group.is_none()));
+    }
+
+    #[test]
+    fn advantage_rolls_twice() {
+        let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let combatants = vec![Combatant::new("Rogue", 3, RollType::Advantage, None)];
+        let order = Initiative::new(combatants, false).roll_initiative_from_rng(rng);
+        assert!(order.current().unwrap().roll.second_roll.is_some());
+    }
+
+    #[test]
+    fn advancing_wraps_into_next_round() {
+        let combatants = vec![
+            Combatant::new("Fighter", 1, RollType::Regular, None),
+            Combatant::new("Wizard", 2, RollType::Regular, None),
+            Combatant::new("Rogue", 3, RollType::Regular, None),
+        ];
+        let mut order = Initiative::new(combatants, false).roll_initiative();
+        let first = order.current().unwrap().names.clone();
+        assert_eq!(order.round(), 1);
+        order.advance();
+        order.advance();
+        assert_eq!(order.turn(), 2);
+        let next = order.advance().unwrap().names.clone();
+        assert_eq!(next, first);
+        assert_eq!(order.round(), 2);
+    }
+
+    #[test]
+    fn advancing_empty_order() {
+        let mut order = Initiative::new(Vec::new(), false).roll_initiative();
+        assert!(order.advance().is_none());
+        assert_eq!(order.round(), 1);
+    }
+}
@@ -0,0 +1,255 @@
+use crate::{
+    dice::{Dice, Operation, RollType},
+    dice_result::RollResult,
+};
+
+use rand::Rng;
+
+/// The highest action score, regardless of the action die, stat and adds.
+const MAXIMUM_ACTION_SCORE: i32 = 10;
+
+/// Represents how an Ironsworn action roll turned out.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum IronswornOutcome {
+    /// The action score beats neither challenge die.
+    Miss,
+    /// The action score beats one challenge die.
+    WeakHit,
+    /// The action score beats both challenge dice.
+    StrongHit,
+}
+
+/// Represents an Ironsworn or Starforged action roll, a d6 action die plus a stat and adds against two d10 challenge dice.
+#[derive(Clone, PartialEq, Debug)]
+pub struct IronswornRoll {
+    action_die: Dice,
+    challenge_dice: Dice,
+    momentum: i32,
+}
+
+impl IronswornRoll {
+    /// Creates a new `IronswornRoll`. Negative momentum cancels the action die when they match.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::ironsworn::IronswornRoll;
+    ///
+    /// // Edge 2, an add of 1 and momentum of 5
+    /// let roll = IronswornRoll::new(2, 1, 5);
+    /// ```
+    #[must_use]
+    pub fn new(stat: i32, adds: i32, momentum: i32) -> Self {
+        IronswornRoll {
+            action_die: Dice::new(
+                1,
+                6,
+                Some(stat + adds),
+                RollType::Regular,
+                Operation::Addition,
+            ),
+            challenge_dice: Dice::new(2, 10, None, RollType::Regular, Operation::Addition),
+            momentum,
+        }
+    }
+
+    /// Makes the action roll. Using underlying OS RNG for the dice roll.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::ironsworn::IronswornRoll;
+    ///
+    /// let result = IronswornRoll::new(2, 0, 2).roll_action();
+    /// assert!(result.action_score <= 10);
+    /// ```
+    #[must_use]
+    pub fn roll_action(&self) -> IronswornResult {
+        let mut rng = rand::thread_rng();
+        self.roll_action_from_rng(&mut rng)
+    }
+
+    /// Makes the action roll, the action die followed by the challenge dice. Uses a source of RNG passed in. Useful for testing.
+    ///
+    /// # Examples
+    /// ```
+    /// use rand::SeedableRng;
+    /// use dnd_dice_roller::ironsworn::IronswornRoll;
+    ///
+    /// let rng = rand_pcg::Pcg64Mcg::seed_from_u64(42);
+    /// let result = IronswornRoll::new(3, 0, 2).roll_action_from_rng(rng);
+    /// assert_eq!(result.action_score, 5);
+    /// ```
+    pub fn roll_action_from_rng<R: Rng + Sized>(&self, mut rng: R) -> IronswornResult {
+        let action_roll = self.action_die.roll_dice_from_rng(&mut rng);
+        let challenge_roll = self.challenge_dice.roll_dice_from_rng(&mut rng);
+
+        #[allow(clippy::cast_possible_wrap)]
+        let action_die = action_roll.first_roll[0] as i32;
+        // Negative momentum matching the action die cancels it
+        let action_die_cancelled = self.momentum < 0 && action_die == -self.momentum;
+        let action_die_value = if action_die_cancelled { 0 } else { action_die };
+        let action_score =
+            (action_die_value + self.action_die.modifier.unwrap_or(0)).min(MAXIMUM_ACTION_SCORE);
+
+        IronswornResult {
+            action_roll,
+            challenge_roll,
+            action_die_cancelled,
+            action_score,
+            momentum_burned: false,
+        }
+    }
+}
+
+/// Represents the result of an `IronswornRoll`.
+#[derive(PartialEq, Debug)]
+pub struct IronswornResult {
+    /// The roll of the action die, including the stat and adds.
+    pub action_roll: RollResult,
+    /// The roll of both challenge dice.
+    pub challenge_roll: RollResult,
+    /// Whether negative momentum cancelled the action die.
+    pub action_die_cancelled: bool,
+    /// The action die, stat and adds, to a maximum of 10. The momentum when momentum has been burned.
+    pub action_score: i32,
+    /// Whether momentum has been burned to replace the action score.
+    pub momentum_burned: bool,
+}
+
+impl IronswornResult {
+    /// The values of the two challenge dice.
+    #[must_use]
+    #[allow(clippy::cast_possible_wrap)]
+    pub fn challenge_dice(&self) -> [i32; 2] {
+        [
+            self.challenge_roll.first_roll[0] as i32,
+            self.challenge_roll.first_roll[1] as i32,
+        ]
+    }
+
+    /// How the roll turned out. A tie with a challenge die does not beat it.
+    #[must_use]
+    pub fn outcome(&self) -> IronswornOutcome {
+        Self::outcome_for(self.action_score, self.challenge_dice())
+    }
+
+    /// Whether both challenge dice show the same value, which makes a strong hit or miss more dramatic.
+    #[must_use]
+    pub fn is_match(&self) -> bool {
+        let [first, second] = self.challenge_dice();
+        first == second
+    }
+
+    /// Whether burning the momentum given would improve the outcome.
+    ///
+    /// # Examples
+    /// ```
+    /// use rand::SeedableRng;
+    /// use dnd_dice_roller::ironsworn::{IronswornOutcome, IronswornRoll};
+    ///
+    /// let rng = rand_pcg::Pcg64Mcg::seed_from_u64(42);
+    /// let result = IronswornRoll::new(1, 0, 10).roll_action_from_rng(rng);
+    /// if result.can_burn_momentum(10) {
+    ///     let burned = result.burn_momentum(10);
+    ///     assert_eq!(burned.outcome(), IronswornOutcome::StrongHit);
+    /// }
+    /// ```
+    #[must_use]
+    pub fn can_burn_momentum(&self, momentum: i32) -> bool {
+        Self::outcome_for(momentum, self.challenge_dice()) > self.outcome()
+    }
+
+    /// Burns momentum, replacing the action score with the momentum given. Afterwards momentum should be reset.
+    #[must_use]
+    pub fn burn_momentum(self, momentum: i32) -> Self {
+        IronswornResult {
+            action_score: momentum,
+            momentum_burned: true,
+            ..self
+        }
+    }
+
+    fn outcome_for(action_score: i32, challenge_dice: [i32; 2]) -> IronswornOutcome {
+        match challenge_dice
+            .iter()
+            .filter(|challenge_die| action_score > **challenge_die)
+            .count()
+        {
+            2 => IronswornOutcome::StrongHit,
+            1 => IronswornOutcome::WeakHit,
+            _ => IronswornOutcome::Miss,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use rand::SeedableRng;
+
+    const SEED: u64 = 42;
+
+    fn result(action_score: i32, challenge_dice: [u32; 2]) -> IronswornResult {
+        #[allow(clippy::cast_possible_wrap)]
+        let total = (challenge_dice[0] + challenge_dice[1]) as i32;
+        IronswornResult {
+            action_roll: RollResult::new(vec![1], None, action_score),
+            challenge_roll: RollResult::new(challenge_dice.to_vec(), None, total),
+            action_die_cancelled: false,
+            action_score,
+            momentum_burned: false,
+        }
+    }
+
+    #[test]
+    fn produces_predictable_results() {
+        let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let result = IronswornRoll::new(2, 1, 2).roll_action_from_rng(rng);
+        assert_eq!(result.action_roll.first_roll, vec![2]);
+        assert_eq!(result.action_score, 5);
+        assert_eq!(result.challenge_roll.first_roll.len(), 2);
+    }
+
+    #[test]
+    fn classifies_outcomes() {
+        assert_eq!(result(7, [3, 6]).outcome(), IronswornOutcome::StrongHit);
+        assert_eq!(result(6, [3, 6]).outcome(), IronswornOutcome::WeakHit);
+        assert_eq!(result(3, [3, 6]).outcome(), IronswornOutcome::Miss);
+    }
+
+    #[test]
+    fn detects_matches() {
+        assert!(result(7, [4, 4]).is_match());
+        assert!(!result(7, [4, 5]).is_match());
+    }
+
+    #[test]
+    fn action_score_is_capped_at_ten() {
+        let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let result = IronswornRoll::new(9, 3, 0).roll_action_from_rng(rng);
+        assert_eq!(result.action_score, 10);
+    }
+
+    #[test]
+    fn negative_momentum_cancels_matching_action_die() {
+        let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let result = IronswornRoll::new(2, 0, -2).roll_action_from_rng(rng);
+        assert!(result.action_die_cancelled);
+        assert_eq!(result.action_score, 2);
+
+        let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let result = IronswornRoll::new(2, 0, -3).roll_action_from_rng(rng);
+        assert!(!result.action_die_cancelled);
+        assert_eq!(result.action_score, 4);
+    }
+
+    #[test]
+    fn burning_momentum_replaces_action_score() {
+        let missed = result(4, [5, 8]);
+        assert!(missed.can_burn_momentum(6));
+        assert!(!missed.can_burn_momentum(5));
+        let burned = missed.burn_momentum(9);
+        assert!(burned.momentum_burned);
+        assert_eq!(burned.outcome(), IronswornOutcome::StrongHit);
+    }
+}
@@ -0,0 +1,253 @@
+use crate::{dice_result::DiceSetResults, error::DiceError, roll::Roll};
+
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::io::{BufRead, Write};
+use std::str::FromStr;
+
+/// The name of the RNG algorithm entries are recorded with, `rand_pcg::Pcg64Mcg` seeded by `SeedableRng::seed_from_u64`.
+pub const PCG64MCG: &str = "Pcg64Mcg";
+
+/// Represents a single recorded roll, with everything needed to replay it.
+#[derive(Serialize, Deserialize, PartialEq, Debug)]
+pub struct JournalEntry {
+    /// The dice input string which was rolled e.g. `2d20 + 1`.
+    pub expression: String,
+    /// The RNG algorithm the roll was made with.
+    pub rng: String,
+    /// The seed the RNG was created from.
+    pub seed: u64,
+    /// The results of the roll.
+    pub results: Vec<DiceSetResults>,
+}
+
+impl JournalEntry {
+    /// Rolls a dice input string with a `Pcg64Mcg` RNG created from the seed, and records the results.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::journal::JournalEntry;
+    /// # use dnd_dice_roller::error::DiceError;
+    ///
+    /// let entry = JournalEntry::record("3d6 + 1", 42)?;
+    /// assert_eq!(entry.results[0].final_result, 14);
+    /// # Ok::<(), DiceError>(())
+    /// ```
+    /// # Errors
+    /// Errors can occur if the dice input string is in the wrong format `DiceError::ParseError`.
+    pub fn record(expression: &str, seed: u64) -> Result<Self, DiceError> {
+        let roll = Roll::from_str(expression)?;
+        Ok(JournalEntry {
+            expression: expression.to_string(),
+            rng: PCG64MCG.to_string(),
+            seed,
+            results: roll.roll_from_rng(rand_pcg::Pcg64Mcg::seed_from_u64(seed)),
+        })
+    }
+
+    /// Rolls the expression again with the recorded RNG and seed.
+    ///
+    /// # Errors
+    /// Errors with `DiceError::UnsupportedRng` if the entry was recorded with an unknown RNG,
+    /// or `DiceError::ParseError` if the expression can no longer be parsed.
+    pub fn replay(&self) -> Result<Vec<DiceSetResults>, DiceError> {
+        if self.rng != PCG64MCG {
+            return Err(DiceError::UnsupportedRng(self.rng.clone()));
+        }
+        let roll = Roll::from_str(&self.expression)?;
+        Ok(roll.roll_from_rng(rand_pcg::Pcg64Mcg::seed_from_u64(self.seed)))
+    }
+
+    /// Replays the entry and checks it reproduces the recorded results.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::journal::JournalEntry;
+    /// # use dnd_dice_roller::error::DiceError;
+    ///
+    /// let entry = JournalEntry::record("d20, d20", 7)?;
+    /// entry.verify()?;
+    /// # Ok::<(), DiceError>(())
+    /// ```
+    /// # Errors
+    /// Errors with `DiceError::ReplayMismatch` if the results differ, or any error from `JournalEntry::replay`.
+    pub fn verify(&self) -> Result<(), DiceError> {
+        if self.replay()? == self.results {
+            Ok(())
+        } else {
+            Err(DiceError::ReplayMismatch(self.expression.clone()))
+        }
+    }
+}
+
+/// Represents a journal of rolls which can be saved as JSON Lines, one `JournalEntry` per line.
+#[derive(PartialEq, Debug, Default)]
+pub struct RollJournal {
+    entries: Vec<JournalEntry>,
+}
+
+impl RollJournal {
+    /// Creates an empty `RollJournal`.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The recorded entries, oldest first.
+    #[must_use]
+    pub fn entries(&self) -> &[JournalEntry] {
+        &self.entries
+    }
+
+    /// Rolls a dice input string with a random seed and records it. Using underlying OS RNG for the seed.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::journal::RollJournal;
+    /// # use dnd_dice_roller::error::DiceError;
+    ///
+    /// let mut journal = RollJournal::new();
+    /// let entry = journal.record("d20 + 5")?;
+    /// entry.verify()?;
+    /// # Ok::<(), DiceError>(())
+    /// ```
+    /// # Errors
+    /// Errors can occur if the dice input string is in the wrong format `DiceError::ParseError`.
+    pub fn record(&mut self, expression: &str) -> Result<&JournalEntry, DiceError> {
+        self.record_with_seed(expression, rand::thread_rng().gen())
+    }
+
+    /// Rolls a dice input string with the seed given and records it.
+    ///
+    /// # Errors
+    /// Errors can occur if the dice input string is in the wrong format `DiceError::ParseError`.
+    pub fn record_with_seed(
+        &mut self,
+        expression: &str,
+        seed: u64,
+    ) -> Result<&JournalEntry, DiceError> {
+        let entry = JournalEntry::record(expression, seed)?;
+        self.entries.push(entry);
+        Ok(&self.entries[self.entries.len() - 1])
+    }
+
+    /// Replays every entry and checks each reproduces its recorded results.
+    ///
+    /// # Errors
+    /// Errors with the first error from `JournalEntry::verify`.
+    pub fn verify(&self) -> Result<(), DiceError> {
+        self.entries.iter().try_for_each(JournalEntry::verify)
+    }
+
+    /// Writes every entry as JSON Lines.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::journal::RollJournal;
+    /// # use dnd_dice_roller::error::DiceError;
+    ///
+    /// let mut journal = RollJournal::new();
+    /// journal.record_with_seed("2d6", 1)?;
+    /// journal.record_with_seed("d20 a", 2)?;
+    ///
+    /// let mut output = Vec::new();
+    /// journal.write_jsonl(&mut output)?;
+    /// let read = RollJournal::read_jsonl(output.as_slice())?;
+    /// assert_eq!(read, journal);
+    /// # Ok::<(), DiceError>(())
+    /// ```
+    /// # Errors
+    /// Errors with `DiceError::JournalIo` if writing fails.
+    pub fn write_jsonl<W: Write>(&self, mut writer: W) -> Result<(), DiceError> {
+        for entry in &self.entries {
+            serde_json::to_writer(&mut writer, entry)
+                .map_err(|error| DiceError::JournalIo(error.into()))?;
+            writer.write_all(b"\n").map_err(DiceError::JournalIo)?;
+        }
+        Ok(())
+    }
+
+    /// Reads a journal from JSON Lines. Blank lines are skipped.
+    ///
+    /// # Errors
+    /// Errors with `DiceError::JournalIo` if reading fails, or `DiceError::Journal` if a line is not a valid entry.
+    pub fn read_jsonl<R: BufRead>(reader: R) -> Result<Self, DiceError> {
+        let mut entries = Vec::new();
+        for (index, line) in reader.lines().enumerate() {
+            let line = line.map_err(DiceError::JournalIo)?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry = serde_json::from_str(&line).map_err(|error| DiceError::Journal {
+                line: index + 1,
+                reason: error.to_string(),
+            })?;
+            entries.push(entry);
+        }
+        Ok(RollJournal { entries })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SEED: u64 = 42;
+
+    #[test]
+    fn records_the_same_results_as_the_tests_rng() {
+        let entry = JournalEntry::record("1d6 a", SEED).expect("Valid input");
+        assert_eq!(entry.rng, PCG64MCG);
+        assert_eq!(entry.results[0].final_result, 6);
+    }
+
+    #[test]
+    fn writes_one_entry_per_line() {
+        let mut journal = RollJournal::new();
+        journal.record_with_seed("2d6", SEED).expect("Valid input");
+        journal
+            .record_with_seed("4dF + 1", SEED)
+            .expect("Valid input");
+        let mut output = Vec::new();
+        journal.write_jsonl(&mut output).expect("Writes");
+        let output = String::from_utf8(output).expect("Valid UTF-8");
+        assert_eq!(output.lines().count(), 2);
+        assert!(output.starts_with(r#"{"expression":"2d6","rng":"Pcg64Mcg","seed":42,"#));
+    }
+
+    #[test]
+    fn round_trips_and_replays() {
+        let mut journal = RollJournal::new();
+        for expression in &["3d6", "2d{2,skull}", "7k3e + 2, d20 d"] {
+            journal.record(expression).expect("Valid input");
+        }
+        let mut output = Vec::new();
+        journal.write_jsonl(&mut output).expect("Writes");
+        let read = RollJournal::read_jsonl(output.as_slice()).expect("Reads");
+        assert_eq!(read, journal);
+        assert!(read.verify().is_ok());
+    }
+
+    #[test]
+    fn detects_tampered_results() {
+        let mut entry = JournalEntry::record("d20", SEED).expect("Valid input");
+        entry.results[0].final_result += 1;
+        assert!(matches!(entry.verify(), Err(DiceError::ReplayMismatch(_))));
+    }
+
+    #[test]
+    fn rejects_unknown_rngs() {
+        let mut entry = JournalEntry::record("d20", SEED).expect("Valid input");
+        entry.rng = "Mt19937".to_string();
+        assert!(matches!(entry.replay(), Err(DiceError::UnsupportedRng(_))));
+    }
+
+    #[test]
+    fn reports_invalid_lines() {
+        let input = "\n{\"expression\":\"d6\"}\n";
+        match RollJournal::read_jsonl(input.as_bytes()) {
+            Err(DiceError::Journal { line, .. }) => assert_eq!(line, 2),
+            other => panic!("Expected a journal error, got {:?}", other),
+        }
+    }
+}
@@ -1,9 +1,53 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(clippy::all, clippy::pedantic)]
 #![allow(clippy::module_name_repetitions)]
 #![warn(missing_doc_code_examples)]
 
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub mod audit;
+#[cfg(feature = "std")]
+pub mod blades;
+#[cfg(feature = "std")]
+pub mod death_saves;
 pub mod dice;
 pub mod dice_result;
 pub mod dice_set;
+pub mod die_roller;
+pub mod distribution;
 pub mod error;
+#[cfg(feature = "fairness")]
+pub mod fairness;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "std")]
+pub mod hit_points;
+#[cfg(feature = "std")]
+pub mod initiative;
+#[cfg(feature = "std")]
+pub mod ironsworn;
+#[cfg(feature = "journal")]
+pub mod journal;
+#[cfg(feature = "std")]
+pub mod narrative;
+mod notation;
+#[cfg(feature = "std")]
+pub mod outcome;
+#[cfg(feature = "std")]
+pub mod percentile;
+pub mod physical;
+#[cfg(feature = "python")]
+pub mod python;
 pub mod roll;
+#[cfg(feature = "std")]
+pub mod savage_worlds;
+pub mod scripted_rng;
+#[cfg(feature = "std")]
+pub mod table;
+#[cfg(any(feature = "csv", feature = "json", feature = "toml"))]
+pub mod table_file;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "std")]
+pub mod year_zero;
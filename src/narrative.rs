@@ -0,0 +1,504 @@
+use crate::{error::DiceError, notation::parse_error};
+
+use rand::Rng;
+use std::fmt;
+use std::str::FromStr;
+
+use NarrativeSymbol::{Advantage, Despair, Failure, Success, Threat, Triumph};
+
+/// The most dice a pool parsed from a dice input string may contain.
+const MAX_POOL_SIZE: u32 = 100;
+
+/// Represents a symbol shown on the face of a narrative dice.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NarrativeSymbol {
+    /// Cancels one `Failure`.
+    Success,
+    /// Cancels one `Success`.
+    Failure,
+    /// Cancels one `Threat`.
+    Advantage,
+    /// Cancels one `Advantage`.
+    Threat,
+    /// Counts as a `Success` which cannot be cancelled by a `Despair`.
+    Triumph,
+    /// Counts as a `Failure` which cannot be cancelled by a `Triumph`.
+    Despair,
+}
+
+impl fmt::Display for NarrativeSymbol {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Success => "success",
+            Failure => "failure",
+            Advantage => "advantage",
+            Threat => "threat",
+            Triumph => "triumph",
+            Despair => "despair",
+        };
+        write!(f, "{name}")
+    }
+}
+
+const BOOST: [&[NarrativeSymbol]; 6] = [
+    &[],
+    &[],
+    &[Success],
+    &[Success, Advantage],
+    &[Advantage, Advantage],
+    &[Advantage],
+];
+const ABILITY: [&[NarrativeSymbol]; 8] = [
+    &[],
+    &[Success],
+    &[Success],
+    &[Success, Success],
+    &[Advantage],
+    &[Advantage],
+    &[Success, Advantage],
+    &[Advantage, Advantage],
+];
+const PROFICIENCY: [&[NarrativeSymbol]; 12] = [
+    &[],
+    &[Success],
+    &[Success],
+    &[Success, Success],
+    &[Success, Success],
+    &[Advantage],
+    &[Success, Advantage],
+    &[Success, Advantage],
+    &[Success, Advantage],
+    &[Advantage, Advantage],
+    &[Advantage, Advantage],
+    &[Triumph],
+];
+const SETBACK: [&[NarrativeSymbol]; 6] = [&[], &[], &[Failure], &[Failure], &[Threat], &[Threat]];
+const DIFFICULTY: [&[NarrativeSymbol]; 8] = [
+    &[],
+    &[Failure],
+    &[Failure, Failure],
+    &[Threat],
+    &[Threat],
+    &[Threat],
+    &[Threat, Threat],
+    &[Failure, Threat],
+];
+const CHALLENGE: [&[NarrativeSymbol]; 12] = [
+    &[],
+    &[Failure],
+    &[Failure],
+    &[Failure, Failure],
+    &[Failure, Failure],
+    &[Threat],
+    &[Threat],
+    &[Failure, Threat],
+    &[Failure, Threat],
+    &[Threat, Threat],
+    &[Threat, Threat],
+    &[Despair],
+];
+
+/// Represents one of the predefined narrative dice used by Genesys and the Star Wars roleplaying games.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NarrativeDie {
+    /// The blue d6, written as `b`.
+    Boost,
+    /// The green d8, written as `g`.
+    Ability,
+    /// The yellow d12, written as `y`.
+    Proficiency,
+    /// The black d6, written as `k`.
+    Setback,
+    /// The purple d8, written as `p`.
+    Difficulty,
+    /// The red d12, written as `r`.
+    Challenge,
+}
+
+impl NarrativeDie {
+    /// The symbols on each face of the dice, in order.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::narrative::{NarrativeDie, NarrativeSymbol};
+    ///
+    /// let faces = NarrativeDie::Proficiency.faces();
+    /// assert_eq!(faces.len(), 12);
+    /// assert_eq!(faces[11], &[NarrativeSymbol::Triumph]);
+    /// ```
+    #[must_use]
+    pub fn faces(self) -> &'static [&'static [NarrativeSymbol]] {
+        match self {
+            NarrativeDie::Boost => &BOOST,
+            NarrativeDie::Ability => &ABILITY,
+            NarrativeDie::Proficiency => &PROFICIENCY,
+            NarrativeDie::Setback => &SETBACK,
+            NarrativeDie::Difficulty => &DIFFICULTY,
+            NarrativeDie::Challenge => &CHALLENGE,
+        }
+    }
+
+    /// The letter used for the dice in dice input strings.
+    #[must_use]
+    pub fn letter(self) -> char {
+        match self {
+            NarrativeDie::Boost => 'b',
+            NarrativeDie::Ability => 'g',
+            NarrativeDie::Proficiency => 'y',
+            NarrativeDie::Setback => 'k',
+            NarrativeDie::Difficulty => 'p',
+            NarrativeDie::Challenge => 'r',
+        }
+    }
+
+    fn from_letter(letter: char) -> Option<Self> {
+        match letter.to_ascii_lowercase() {
+            'b' => Some(NarrativeDie::Boost),
+            'g' => Some(NarrativeDie::Ability),
+            'y' => Some(NarrativeDie::Proficiency),
+            'k' => Some(NarrativeDie::Setback),
+            'p' => Some(NarrativeDie::Difficulty),
+            'r' => Some(NarrativeDie::Challenge),
+            _ => None,
+        }
+    }
+}
+
+/// Represents a pool of narrative dice rolled together, whose symbols cancel each other out.
+#[derive(Clone, PartialEq, Debug)]
+pub struct NarrativeDicePool {
+    dice: Vec<NarrativeDie>,
+}
+
+impl NarrativeDicePool {
+    /// Creates a new `NarrativeDicePool`.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::narrative::{NarrativeDicePool, NarrativeDie};
+    ///
+    /// let pool = NarrativeDicePool::new(vec![NarrativeDie::Ability, NarrativeDie::Difficulty]);
+    /// ```
+    #[must_use]
+    pub fn new(dice: Vec<NarrativeDie>) -> Self {
+        Self { dice }
+    }
+
+    /// The dice in the pool.
+    #[must_use]
+    pub fn dice(&self) -> &[NarrativeDie] {
+        &self.dice
+    }
+
+    /// Rolls every dice in the pool. Using underlying OS RNG for the dice roll.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::str::FromStr;
+    /// use dnd_dice_roller::narrative::NarrativeDicePool;
+    /// # use dnd_dice_roller::error::DiceError;
+    ///
+    /// let pool = NarrativeDicePool::from_str("2g1y 2p")?;
+    /// let result = pool.roll_pool();
+    /// # Ok::<(), DiceError>(())
+    /// ```
+    #[must_use]
+    pub fn roll_pool(&self) -> NarrativeResult {
+        let mut rng = rand::thread_rng();
+        self.roll_pool_from_rng(&mut rng)
+    }
+
+    /// Rolls every dice in the pool. Uses a source of RNG passed in. Useful for testing.
+    ///
+    /// # Examples
+    /// ```
+    /// use rand::SeedableRng;
+    /// use std::str::FromStr;
+    /// use dnd_dice_roller::narrative::NarrativeDicePool;
+    /// # use dnd_dice_roller::error::DiceError;
+    ///
+    /// let rng = rand_pcg::Pcg64Mcg::seed_from_u64(42);
+    /// let pool = NarrativeDicePool::from_str("2g1y 2p")?;
+    /// let result = pool.roll_pool_from_rng(rng);
+    /// assert_eq!(result.rolls.len(), 5);
+    /// # Ok::<(), DiceError>(())
+    /// ```
+    pub fn roll_pool_from_rng<R: Rng + Sized>(&self, mut rng: R) -> NarrativeResult {
+        let rolls = self
+            .dice
+            .iter()
+            .map(|die| {
+                let faces = die.faces();
+                let index = rng.gen_range(0..faces.len());
+                NarrativeDieRoll {
+                    die: *die,
+                    face: index + 1,
+                    symbols: faces[index].to_vec(),
+                }
+            })
+            .collect();
+        NarrativeResult { rolls }
+    }
+}
+
+impl FromStr for NarrativeDicePool {
+    type Err = DiceError;
+
+    /// Creates a `NarrativeDicePool` from an input string of counts and dice letters e.g. `2g1y 2p`.
+    /// The count may be omitted for a single dice.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::str::FromStr;
+    /// use dnd_dice_roller::narrative::{NarrativeDicePool, NarrativeDie};
+    /// # use dnd_dice_roller::error::DiceError;
+    ///
+    /// let pool = NarrativeDicePool::from_str("g 2p")?;
+    /// assert_eq!(pool.dice(), &[NarrativeDie::Ability, NarrativeDie::Difficulty, NarrativeDie::Difficulty]);
+    /// # Ok::<(), DiceError>(())
+    /// ```
+    /// # Errors
+    /// Errors can occur if the dice input string is in the wrong format, or has more than 100 dice `DiceError::ParseError`.
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let mut dice = Vec::new();
+        let mut count = String::new();
+        for c in input.chars().filter(|c| !c.is_whitespace()) {
+            if c.is_ascii_digit() {
+                count.push(c);
+                continue;
+            }
+            let letter_die = NarrativeDie::from_letter(c).ok_or_else(|| parse_error(input))?;
+            let number: u32 = if count.is_empty() {
+                1
+            } else {
+                count.parse().map_err(|_| parse_error(input))?
+            };
+            if dice.len() + number as usize > MAX_POOL_SIZE as usize {
+                return Err(parse_error(input));
+            }
+            dice.extend(std::iter::repeat_n(letter_die, number as usize));
+            count.clear();
+        }
+        if !count.is_empty() || dice.is_empty() {
+            return Err(parse_error(input));
+        }
+        Ok(Self::new(dice))
+    }
+}
+
+/// Represents the roll of a single narrative dice.
+#[derive(Clone, PartialEq, Debug)]
+pub struct NarrativeDieRoll {
+    /// The dice rolled.
+    pub die: NarrativeDie,
+    /// The 1-based position of the face rolled.
+    pub face: usize,
+    /// The symbols on the face rolled.
+    pub symbols: Vec<NarrativeSymbol>,
+}
+
+/// Represents the result of rolling a `NarrativeDicePool`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct NarrativeResult {
+    /// The roll of each dice in the pool, in order.
+    pub rolls: Vec<NarrativeDieRoll>,
+}
+
+impl NarrativeResult {
+    /// The number of a symbol rolled before cancellation.
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_possible_wrap)]
+    pub fn count(&self, symbol: NarrativeSymbol) -> i32 {
+        self.rolls
+            .iter()
+            .flat_map(|roll| roll.symbols.iter())
+            .filter(|rolled| **rolled == symbol)
+            .count() as i32
+    }
+
+    /// Successes, including triumphs, less failures, including despairs. Negative when failures remain.
+    ///
+    /// # Examples
+    /// ```
+    /// use rand::SeedableRng;
+    /// use std::str::FromStr;
+    /// use dnd_dice_roller::narrative::NarrativeDicePool;
+    /// # use dnd_dice_roller::error::DiceError;
+    ///
+    /// let rng = rand_pcg::Pcg64Mcg::seed_from_u64(42);
+    /// let result = NarrativeDicePool::from_str("3g 2p")?.roll_pool_from_rng(rng);
+    /// assert_eq!(result.is_success(), result.net_successes() > 0);
+    /// # Ok::<(), DiceError>(())
+    /// ```
+    #[must_use]
+    pub fn net_successes(&self) -> i32 {
+        self.count(Success) + self.count(Triumph) - self.count(Failure) - self.count(Despair)
+    }
+
+    /// Advantages less threats. Negative when threats remain.
+    #[must_use]
+    pub fn net_advantages(&self) -> i32 {
+        self.count(Advantage) - self.count(Threat)
+    }
+
+    /// The number of triumphs rolled, which are never cancelled.
+    #[must_use]
+    pub fn triumphs(&self) -> i32 {
+        self.count(Triumph)
+    }
+
+    /// The number of despairs rolled, which are never cancelled.
+    #[must_use]
+    pub fn despairs(&self) -> i32 {
+        self.count(Despair)
+    }
+
+    /// Whether at least one success remains after cancellation.
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        self.net_successes() > 0
+    }
+
+    /// The symbols remaining after cancellation e.g. two successes, one threat and one triumph.
+    /// Successes and failures include those from triumphs and despairs, which are also listed in their own right.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::narrative::{NarrativeDie, NarrativeDieRoll, NarrativeResult, NarrativeSymbol};
+    ///
+    /// let result = NarrativeResult {
+    ///     rolls: vec![
+    ///         NarrativeDieRoll { die: NarrativeDie::Proficiency, face: 12, symbols: vec![NarrativeSymbol::Triumph] },
+    ///         NarrativeDieRoll { die: NarrativeDie::Difficulty, face: 8, symbols: vec![NarrativeSymbol::Failure, NarrativeSymbol::Threat] },
+    ///     ],
+    /// };
+    /// assert_eq!(result.net_symbols(), vec![NarrativeSymbol::Threat, NarrativeSymbol::Triumph]);
+    /// ```
+    #[must_use]
+    pub fn net_symbols(&self) -> Vec<NarrativeSymbol> {
+        let mut symbols = Vec::new();
+        let mut push = |symbol, count: i32| {
+            symbols.extend(std::iter::repeat_n(symbol, count.unsigned_abs() as usize));
+        };
+        let successes = self.net_successes();
+        push(if successes > 0 { Success } else { Failure }, successes);
+        let advantages = self.net_advantages();
+        push(if advantages > 0 { Advantage } else { Threat }, advantages);
+        push(Triumph, self.triumphs());
+        push(Despair, self.despairs());
+        symbols
+    }
+}
+
+impl fmt::Display for NarrativeResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let symbols: Vec<String> = self.net_symbols().iter().map(ToString::to_string).collect();
+        if symbols.is_empty() {
+            write!(f, "no symbols")
+        } else {
+            write!(f, "{}", symbols.join(", "))
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use rand::SeedableRng;
+
+    const SEED: u64 = 42;
+
+    fn result(symbols: &[&[NarrativeSymbol]]) -> NarrativeResult {
+        NarrativeResult {
+            rolls: symbols
+                .iter()
+                .map(|symbols| NarrativeDieRoll {
+                    die: NarrativeDie::Ability,
+                    face: 1,
+                    symbols: symbols.to_vec(),
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn parses_pool() {
+        let pool = NarrativeDicePool::from_str("2g1y 2p r").expect("Valid input");
+        assert_eq!(
+            pool.dice(),
+            &[
+                NarrativeDie::Ability,
+                NarrativeDie::Ability,
+                NarrativeDie::Proficiency,
+                NarrativeDie::Difficulty,
+                NarrativeDie::Difficulty,
+                NarrativeDie::Challenge,
+            ]
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_pool() {
+        assert!(NarrativeDicePool::from_str("2x").is_err());
+        assert!(NarrativeDicePool::from_str("2g 3").is_err());
+        assert!(NarrativeDicePool::from_str("").is_err());
+    }
+
+    #[test]
+    fn rejects_oversized_pool() {
+        assert!(NarrativeDicePool::from_str("100g").is_ok());
+        assert!(NarrativeDicePool::from_str("99g 2p").is_err());
+        assert!(NarrativeDicePool::from_str("99999999999g").is_err());
+    }
+
+    #[test]
+    fn symbols_cancel() {
+        let result = result(&[
+            &[Success, Success],
+            &[Advantage],
+            &[Failure, Threat],
+            &[Threat, Threat],
+        ]);
+        assert_eq!(result.net_successes(), 1);
+        assert_eq!(result.net_advantages(), -2);
+        assert_eq!(result.net_symbols(), vec![Success, Threat, Threat]);
+        assert_eq!(format!("{result}"), "success, threat, threat");
+    }
+
+    #[test]
+    fn triumph_and_despair_count_but_do_not_cancel_each_other() {
+        let result = result(&[&[Triumph], &[Despair], &[Failure]]);
+        assert_eq!(result.net_successes(), -1);
+        assert_eq!(result.triumphs(), 1);
+        assert_eq!(result.despairs(), 1);
+        assert!(!result.is_success());
+        assert_eq!(result.net_symbols(), vec![Failure, Triumph, Despair]);
+    }
+
+    #[test]
+    fn triumph_alone_is_a_success() {
+        let result = result(&[&[Triumph]]);
+        assert!(result.is_success());
+        assert_eq!(result.net_symbols(), vec![Success, Triumph]);
+    }
+
+    #[test]
+    fn empty_result_displays_no_symbols() {
+        assert_eq!(
+            format!("{}", result(&[&[Success], &[Failure]])),
+            "no symbols"
+        );
+    }
+
+    #[test]
+    fn rolls_faces_of_each_dice() {
+        let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let pool = NarrativeDicePool::from_str("2g1y 2p").expect("Valid input");
+        let result = pool.roll_pool_from_rng(rng);
+        assert_eq!(result.rolls.len(), 5);
+        for roll in &result.rolls {
+            assert_eq!(roll.symbols, roll.die.faces()[roll.face - 1]);
+        }
+    }
+}
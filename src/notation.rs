@@ -0,0 +1,417 @@
+use crate::{
+    dice::{Dice, Face, NamedDice, Operation, RollType},
+    dice_set::DiceSet,
+    error::DiceError,
+};
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+/// Parses a full dice input string e.g. `4dF+2, d20 + d4` into one `DiceSet` per comma separated group.
+/// Custom dice referred to by name e.g. `2d[averaging]` are looked up in `named_dice`.
+pub(crate) fn parse_dice_sets(
+    input: &str,
+    named_dice: &NamedDice,
+) -> Result<Vec<DiceSet>, DiceError> {
+    let input: String = input.chars().filter(|c| !c.is_whitespace()).collect();
+    split_groups(&input)
+        .into_iter()
+        .map(|group| {
+            split_terms(group)
+                .into_iter()
+                .map(|term| parse_term(term, named_dice))
+                .collect::<Result<Vec<Dice>, DiceError>>()
+                .map(DiceSet::new)
+        })
+        .collect()
+}
+
+pub(crate) fn parse_error(input: &str) -> DiceError {
+    DiceError::ParseError(format!("Unable to parse dice: {input}"))
+}
+
+/// Splits the input on commas, other than those listing the faces of a custom dice.
+fn split_groups(input: &str) -> Vec<&str> {
+    let mut groups = Vec::new();
+    let mut start = 0;
+    let mut depth = 0;
+    for (index, c) in input.char_indices() {
+        match c {
+            '{' | '[' => depth += 1,
+            '}' | ']' => depth -= 1,
+            ',' if depth == 0 => {
+                groups.push(&input[start..index]);
+                start = index + 1;
+            }
+            _ => {}
+        }
+    }
+    groups.push(&input[start..]);
+    groups
+}
+
+/// Splits a group into terms, each of which is a single dice with its sign, modifier and roll type. e.g. `2d6+2-d4a` into `2d6+2` and `-d4a`.
+fn split_terms(group: &str) -> Vec<&str> {
+    let mut terms = Vec::new();
+    let mut start = 0;
+    let mut depth = 0;
+    for (index, c) in group.char_indices() {
+        match c {
+            '{' | '[' => depth += 1,
+            '}' | ']' => depth -= 1,
+            // A sign followed by dice begins a new term, otherwise it begins the modifier of the current term
+            '+' | '-' if depth == 0 && index > start && starts_with_dice(&group[index + 1..]) => {
+                terms.push(&group[start..index]);
+                start = index;
+            }
+            _ => {}
+        }
+    }
+    terms.push(&group[start..]);
+    terms
+}
+
+fn starts_with_dice(input: &str) -> bool {
+    let rest = input.trim_start_matches(|c: char| c.is_ascii_digit());
+    let has_count = rest.len() < input.len();
+    let mut chars = rest.chars();
+    match chars.next() {
+        Some('d' | 'D') => {
+            matches!(chars.next(), Some(c) if c.is_ascii_digit() || matches!(c, 'f' | 'F' | '{' | '['))
+        }
+        Some('k' | 'K') => has_count && matches!(chars.next(), Some(c) if c.is_ascii_digit()),
+        _ => false,
+    }
+}
+
+fn parse_term(term: &str, named_dice: &NamedDice) -> Result<Dice, DiceError> {
+    let (operation, body) = match term.chars().next() {
+        Some('+') => (Operation::Addition, &term[1..]),
+        Some('-') => (Operation::Subtraction, &term[1..]),
+        _ => (Operation::Addition, term),
+    };
+
+    let (count, rest) = split_digits(body);
+    let mut rest_chars = rest.chars();
+    match rest_chars.next() {
+        Some('d' | 'D') => {}
+        Some('k' | 'K') => return parse_roll_and_keep(term, count, &rest[1..], operation),
+        _ => return Err(parse_error(term)),
+    }
+
+    match rest_chars.next() {
+        Some('f' | 'F') => {
+            let number_of_dice = parse_count(count).ok_or_else(|| parse_error(term))?;
+            let (modifier, roll_type) =
+                parse_modifier_and_roll_type(&rest[2..]).ok_or_else(|| parse_error(term))?;
+            Ok(Dice::fudge(number_of_dice, modifier, roll_type, operation))
+        }
+        Some(open @ ('{' | '[')) => {
+            let number_of_dice = parse_count(count).ok_or_else(|| parse_error(term))?;
+            let close = if open == '{' { '}' } else { ']' };
+            let end = rest.find(close).ok_or_else(|| parse_error(term))?;
+            let inner = &rest[2..end];
+            let faces = if open == '{' {
+                parse_faces(inner)
+            } else {
+                named_dice.get(inner).map(<[Face]>::to_vec)
+            }
+            .ok_or_else(|| parse_error(term))?;
+            let (modifier, roll_type) =
+                parse_modifier_and_roll_type(&rest[end + 1..]).ok_or_else(|| parse_error(term))?;
+            Dice::custom(number_of_dice, faces, modifier, roll_type, operation)
+        }
+        _ => parse_standard_term(term, count, &rest[1..], operation),
+    }
+}
+
+/// Parses the kept dice and what follows a roll and keep dice e.g. `3e+5` of `7k3e+5`, where `e` is emphasis which rerolls 1s.
+fn parse_roll_and_keep(
+    term: &str,
+    rolled: &str,
+    rest: &str,
+    operation: Operation,
+) -> Result<Dice, DiceError> {
+    let rolled: u32 = rolled.parse().map_err(|_| parse_error(term))?;
+    let (kept, rest) = split_digits(rest);
+    let kept: u32 = kept.parse().map_err(|_| parse_error(term))?;
+    let (emphasis, rest) = match rest.strip_prefix(['e', 'E']) {
+        Some(rest) => (true, rest),
+        None => (false, rest),
+    };
+    let (modifier, roll_type) =
+        parse_modifier_and_roll_type(rest).ok_or_else(|| parse_error(term))?;
+
+    let dice = Dice::roll_and_keep(rolled, kept, modifier, roll_type, operation);
+    Ok(if emphasis { dice.rerolling(1) } else { dice })
+}
+
+/// Parses the sides and what follows a standard dice e.g. `20+5a` of `2d20+5a`.
+fn parse_standard_term(
+    term: &str,
+    count: &str,
+    rest: &str,
+    operation: Operation,
+) -> Result<Dice, DiceError> {
+    let number_of_dice = parse_count(count).ok_or_else(|| parse_error(term))?;
+    let (sides, rest) = split_digits(rest);
+    let sides: u32 = sides.parse().map_err(|_| parse_error(term))?;
+    let (modifier, roll_type) =
+        parse_modifier_and_roll_type(rest).ok_or_else(|| parse_error(term))?;
+    Ok(Dice::new(
+        number_of_dice,
+        sides,
+        modifier,
+        roll_type,
+        operation,
+    ))
+}
+
+/// Splits the leading digits from the rest of the input e.g. `20+5` into `20` and `+5`.
+fn split_digits(input: &str) -> (&str, &str) {
+    let digits = input.len() - input.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    input.split_at(digits)
+}
+
+/// Parses a comma separated list of faces e.g. `2,4,skull`. Faces which are whole numbers count towards the total.
+fn parse_faces(input: &str) -> Option<Vec<Face>> {
+    input
+        .split(',')
+        .map(|face| match face.parse() {
+            Ok(value) => Some(Face::Number(value)),
+            Err(_) if !face.is_empty() && !face.contains(['{', '}', '[', ']']) => {
+                Some(Face::Symbol(face.to_string()))
+            }
+            Err(_) => None,
+        })
+        .collect()
+}
+
+/// Parses the number of dice, which defaults to one when omitted.
+fn parse_count(count: &str) -> Option<u32> {
+    if count.is_empty() {
+        Some(1)
+    } else {
+        count.parse().ok()
+    }
+}
+
+/// Parses what follows a dice e.g. `+2a` into a modifier and roll type. The entire input must be consumed.
+fn parse_modifier_and_roll_type(input: &str) -> Option<(Option<i32>, RollType)> {
+    let (modifier, rest) = match input.chars().next() {
+        Some(sign @ ('+' | '-')) => {
+            let digits_end = input[1..]
+                .find(|c: char| !c.is_ascii_digit())
+                .map_or(input.len(), |index| index + 1);
+            let value: i32 = input[1..digits_end].parse().ok()?;
+            let modifier = if sign == '-' { -value } else { value };
+            (Some(modifier), &input[digits_end..])
+        }
+        _ => (None, input),
+    };
+
+    let roll_type = match rest.to_ascii_lowercase().as_str() {
+        "" => RollType::Regular,
+        "a" => RollType::Advantage,
+        "d" => RollType::Disadvantage,
+        _ => return None,
+    };
+    Some((modifier, roll_type))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn splits_terms_on_dice_signs_only() {
+        assert_eq!(split_terms("2d6+2-d4a"), vec!["2d6+2", "-d4a"]);
+        assert_eq!(split_terms("-d6-1d"), vec!["-d6-1d"]);
+        assert_eq!(split_terms("4df+2+d6"), vec!["4df+2", "+d6"]);
+        assert_eq!(split_terms("d{-1,1}+d[x]"), vec!["d{-1,1}", "+d[x]"]);
+        assert_eq!(split_terms("7k3e+5-2k1"), vec!["7k3e+5", "-2k1"]);
+    }
+
+    #[test]
+    fn splits_groups_outside_faces() {
+        assert_eq!(split_groups("2d{1,2},d6"), vec!["2d{1,2}", "d6"]);
+    }
+
+    #[test]
+    fn parses_modifiers_and_roll_types() {
+        assert_eq!(
+            parse_modifier_and_roll_type("+2a"),
+            Some((Some(2), RollType::Advantage))
+        );
+        assert_eq!(
+            parse_modifier_and_roll_type("-10"),
+            Some((Some(-10), RollType::Regular))
+        );
+        assert_eq!(
+            parse_modifier_and_roll_type(""),
+            Some((None, RollType::Regular))
+        );
+        assert_eq!(parse_modifier_and_roll_type("x"), None);
+        assert_eq!(parse_modifier_and_roll_type("+"), None);
+    }
+
+    #[test]
+    fn parses_standard_dice() {
+        let dice_sets = parse_dice_sets("2d20 - 2 a + d4, -D6+1d, 10d100", &NamedDice::new())
+            .expect("Valid input");
+        let expected = vec![
+            DiceSet::new(vec![
+                Dice::new(2, 20, Some(-2), RollType::Advantage, Operation::Addition),
+                Dice::new(1, 4, None, RollType::Regular, Operation::Addition),
+            ]),
+            DiceSet::new(vec![Dice::new(
+                1,
+                6,
+                Some(1),
+                RollType::Disadvantage,
+                Operation::Subtraction,
+            )]),
+            DiceSet::new(vec![Dice::new(
+                10,
+                100,
+                None,
+                RollType::Regular,
+                Operation::Addition,
+            )]),
+        ];
+        assert_eq!(dice_sets, expected);
+    }
+
+    #[test]
+    fn rejects_invalid_standard_dice() {
+        let named_dice = NamedDice::new();
+        assert!(parse_dice_sets("", &named_dice).is_err());
+        assert!(parse_dice_sets("6", &named_dice).is_err());
+        assert!(parse_dice_sets("2d", &named_dice).is_err());
+        assert!(parse_dice_sets("d6x", &named_dice).is_err());
+        assert!(parse_dice_sets("d6a+2", &named_dice).is_err());
+        assert!(parse_dice_sets("d6,", &named_dice).is_err());
+        assert!(parse_dice_sets("d99999999999", &named_dice).is_err());
+        assert!(parse_dice_sets("99999999999d6", &named_dice).is_err());
+    }
+
+    #[test]
+    fn parses_fudge_dice() {
+        let dice_sets = parse_dice_sets("4dF+2", &NamedDice::new()).expect("Valid input");
+        let expected = vec![DiceSet::new(vec![Dice::fudge(
+            4,
+            Some(2),
+            RollType::Regular,
+            Operation::Addition,
+        )])];
+        assert_eq!(dice_sets, expected);
+    }
+
+    #[test]
+    fn parses_fudge_dice_mixed_with_standard_dice() {
+        let dice_sets =
+            parse_dice_sets("df - d6 + 1, 2d20 a", &NamedDice::new()).expect("Valid input");
+        let expected = vec![
+            DiceSet::new(vec![
+                Dice::fudge(1, None, RollType::Regular, Operation::Addition),
+                Dice::new(1, 6, Some(1), RollType::Regular, Operation::Subtraction),
+            ]),
+            DiceSet::new(vec![Dice::new(
+                2,
+                20,
+                None,
+                RollType::Advantage,
+                Operation::Addition,
+            )]),
+        ];
+        assert_eq!(dice_sets, expected);
+    }
+
+    #[test]
+    fn parses_inline_custom_dice() {
+        let dice_sets =
+            parse_dice_sets("2d{2, 4, skull} + 1 - d6", &NamedDice::new()).expect("Valid input");
+        let faces = vec![
+            Face::Number(2),
+            Face::Number(4),
+            Face::Symbol("skull".to_string()),
+        ];
+        let expected = vec![DiceSet::new(vec![
+            Dice::custom(2, faces, Some(1), RollType::Regular, Operation::Addition)
+                .expect("Faces given"),
+            Dice::new(1, 6, None, RollType::Regular, Operation::Subtraction),
+        ])];
+        assert_eq!(dice_sets, expected);
+    }
+
+    #[test]
+    fn parses_named_custom_dice() {
+        let faces: Vec<Face> = [2, 3, 3, 4, 4, 5]
+            .iter()
+            .map(|v| Face::Number(*v))
+            .collect();
+        let mut named_dice = NamedDice::new();
+        named_dice
+            .insert("averaging", faces.clone())
+            .expect("Faces given");
+        let dice_sets = parse_dice_sets("3d[averaging]a", &named_dice).expect("Valid input");
+        let expected = vec![DiceSet::new(vec![Dice::custom(
+            3,
+            faces,
+            None,
+            RollType::Advantage,
+            Operation::Addition,
+        )
+        .expect("Faces given")])];
+        assert_eq!(dice_sets, expected);
+    }
+
+    #[test]
+    fn rejects_invalid_custom_dice() {
+        let named_dice = NamedDice::new();
+        assert!(parse_dice_sets("d{}", &named_dice).is_err());
+        assert!(parse_dice_sets("d{1,,2}", &named_dice).is_err());
+        assert!(parse_dice_sets("d{1,2", &named_dice).is_err());
+        assert!(parse_dice_sets("d[unknown]", &named_dice).is_err());
+    }
+
+    #[test]
+    fn parses_roll_and_keep_dice() {
+        let dice_sets =
+            parse_dice_sets("7k3 + 2k2e - 5, 14k12", &NamedDice::new()).expect("Valid input");
+        let expected = vec![
+            DiceSet::new(vec![
+                Dice::roll_and_keep(7, 3, None, RollType::Regular, Operation::Addition),
+                Dice::roll_and_keep(2, 2, Some(-5), RollType::Regular, Operation::Addition)
+                    .rerolling(1),
+            ]),
+            DiceSet::new(vec![Dice::new(
+                10,
+                10,
+                Some(8),
+                RollType::Regular,
+                Operation::Addition,
+            )
+            .exploding()
+            .keep_highest(10)]),
+        ];
+        assert_eq!(dice_sets, expected);
+    }
+
+    #[test]
+    fn rejects_invalid_roll_and_keep_dice() {
+        let named_dice = NamedDice::new();
+        assert!(parse_dice_sets("k3", &named_dice).is_err());
+        assert!(parse_dice_sets("7k", &named_dice).is_err());
+        assert!(parse_dice_sets("7k3x", &named_dice).is_err());
+    }
+
+    #[test]
+    fn rejects_invalid_fudge_dice() {
+        assert!(parse_dice_sets("4dFx", &NamedDice::new()).is_err());
+        assert!(parse_dice_sets("4dF+", &NamedDice::new()).is_err());
+    }
+}
@@ -0,0 +1,218 @@
+use crate::{dice_result::DiceSetResults, distribution::Distribution, error::DiceError};
+
+use std::ops::RangeInclusive;
+
+/// Represents a labelled band of results e.g. a weak hit on 7 to 9.
+#[derive(Clone, PartialEq, Debug)]
+pub struct OutcomeBand {
+    /// The results which produce this outcome. Use `i32::MIN` or `i32::MAX` for an open ended band e.g. `6-` or `10+`.
+    pub range: RangeInclusive<i32>,
+    /// The name of the outcome.
+    pub label: String,
+}
+
+impl OutcomeBand {
+    /// Creates a new `OutcomeBand`.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::outcome::OutcomeBand;
+    ///
+    /// let weak_hit = OutcomeBand::new(7..=9, "Weak hit");
+    /// let strong_hit = OutcomeBand::new(10..=i32::MAX, "Strong hit");
+    /// ```
+    #[must_use]
+    pub fn new(range: RangeInclusive<i32>, label: &str) -> Self {
+        OutcomeBand {
+            range,
+            label: label.to_string(),
+        }
+    }
+}
+
+/// Represents a set of non-overlapping outcome bands which map the total of a roll to a labelled outcome.
+#[derive(Clone, PartialEq, Debug)]
+pub struct OutcomeBands {
+    bands: Vec<OutcomeBand>,
+}
+
+impl OutcomeBands {
+    /// Creates a new `OutcomeBands`. Results not covered by any band have no outcome.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::outcome::{OutcomeBand, OutcomeBands};
+    /// # use dnd_dice_roller::error::DiceError;
+    ///
+    /// let bands = OutcomeBands::new(vec![
+    ///     OutcomeBand::new(i32::MIN..=9, "Failure"),
+    ///     OutcomeBand::new(10..=i32::MAX, "Success"),
+    /// ])?;
+    /// # Ok::<(), DiceError>(())
+    /// ```
+    /// # Errors
+    /// Errors with `DiceError::OutcomeOverlap` if two bands include the same result.
+    pub fn new(mut bands: Vec<OutcomeBand>) -> Result<Self, DiceError> {
+        bands.sort_by_key(|band| *band.range.start());
+        for pair in bands.windows(2) {
+            if pair[1].range.start() <= pair[0].range.end() {
+                return Err(DiceError::OutcomeOverlap {
+                    first: pair[0].label.clone(),
+                    second: pair[1].label.clone(),
+                    value: *pair[1].range.start(),
+                });
+            }
+        }
+        Ok(OutcomeBands { bands })
+    }
+
+    /// The Powered by the Apocalypse move outcomes for 2d6 plus a stat: a miss on 6 or less, a weak hit on 7 to 9 and a strong hit on 10 or more.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::outcome::OutcomeBands;
+    ///
+    /// let bands = OutcomeBands::powered_by_the_apocalypse();
+    /// assert_eq!(bands.outcome(8).map(|band| band.label.as_str()), Some("Weak hit"));
+    /// ```
+    #[must_use]
+    pub fn powered_by_the_apocalypse() -> Self {
+        OutcomeBands {
+            bands: vec![
+                OutcomeBand::new(i32::MIN..=6, "Miss"),
+                OutcomeBand::new(7..=9, "Weak hit"),
+                OutcomeBand::new(10..=i32::MAX, "Strong hit"),
+            ],
+        }
+    }
+
+    /// The bands, ordered from the lowest results to the highest.
+    #[must_use]
+    pub fn bands(&self) -> &[OutcomeBand] {
+        &self.bands
+    }
+
+    /// The band a total falls in, if any.
+    #[must_use]
+    pub fn outcome(&self, total: i32) -> Option<&OutcomeBand> {
+        self.bands.iter().find(|band| band.range.contains(&total))
+    }
+
+    /// The band the final result of a roll falls in, if any.
+    ///
+    /// # Examples
+    /// ```
+    /// use rand::SeedableRng;
+    /// use std::str::FromStr;
+    /// use dnd_dice_roller::outcome::OutcomeBands;
+    /// use dnd_dice_roller::roll::Roll;
+    /// # use dnd_dice_roller::error::DiceError;
+    ///
+    /// let rng = rand_pcg::Pcg64Mcg::seed_from_u64(42);
+    /// let results = Roll::from_str("2d6 + 1")?.roll_from_rng(rng);
+    /// let bands = OutcomeBands::powered_by_the_apocalypse();
+    /// let outcome = bands.outcome_of(&results[0]);
+    /// assert_eq!(outcome.map(|band| band.label.as_str()), Some("Weak hit"));
+    /// # Ok::<(), DiceError>(())
+    /// ```
+    #[must_use]
+    pub fn outcome_of(&self, results: &DiceSetResults) -> Option<&OutcomeBand> {
+        self.outcome(results.final_result)
+    }
+
+    /// The exact probability of each band, in order, given the distribution of a roll's totals.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::str::FromStr;
+    /// use dnd_dice_roller::outcome::OutcomeBands;
+    /// use dnd_dice_roller::roll::Roll;
+    /// # use dnd_dice_roller::error::DiceError;
+    ///
+    /// let roll = Roll::from_str("2d6")?;
+    /// let distribution = roll.dice_sets()[0].distribution();
+    /// let bands = OutcomeBands::powered_by_the_apocalypse();
+    /// let probabilities = bands.probabilities(&distribution);
+    /// assert!((probabilities[0].1 - 15.0 / 36.0).abs() < 1e-9);
+    /// # Ok::<(), DiceError>(())
+    /// ```
+    #[must_use]
+    pub fn probabilities(&self, distribution: &Distribution) -> Vec<(&OutcomeBand, f64)> {
+        self.bands
+            .iter()
+            .map(|band| (band, distribution.probability_in_range(band.range.clone())))
+            .collect()
+    }
+}
+
+impl Default for OutcomeBands {
+    fn default() -> Self {
+        Self::powered_by_the_apocalypse()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::dice::{Dice, Operation, RollType};
+    use crate::dice_set::DiceSet;
+
+    fn label(bands: &OutcomeBands, total: i32) -> Option<&str> {
+        bands.outcome(total).map(|band| band.label.as_str())
+    }
+
+    #[test]
+    fn maps_totals_to_pbta_outcomes() {
+        let bands = OutcomeBands::default();
+        assert_eq!(label(&bands, -1), Some("Miss"));
+        assert_eq!(label(&bands, 6), Some("Miss"));
+        assert_eq!(label(&bands, 7), Some("Weak hit"));
+        assert_eq!(label(&bands, 9), Some("Weak hit"));
+        assert_eq!(label(&bands, 10), Some("Strong hit"));
+        assert_eq!(label(&bands, 15), Some("Strong hit"));
+    }
+
+    #[test]
+    fn pbta_probabilities_with_a_stat() {
+        let dice_set = DiceSet::new(vec![Dice::new(
+            2,
+            6,
+            Some(1),
+            RollType::Regular,
+            Operation::Addition,
+        )]);
+        let bands = OutcomeBands::default();
+        let probabilities = bands.probabilities(&dice_set.distribution());
+        let expected = [10.0 / 36.0, 16.0 / 36.0, 10.0 / 36.0];
+        for ((_, probability), expected) in probabilities.iter().zip(expected.iter()) {
+            assert!((probability - expected).abs() < 1e-9);
+        }
+    }
+
+    #[test]
+    fn uncovered_results_have_no_outcome() {
+        let bands = OutcomeBands::new(vec![OutcomeBand::new(1..=3, "Low")]).expect("Valid bands");
+        assert_eq!(label(&bands, 4), None);
+    }
+
+    #[test]
+    fn overlapping_bands_are_rejected() {
+        let bands = OutcomeBands::new(vec![
+            OutcomeBand::new(5..=10, "High"),
+            OutcomeBand::new(1..=5, "Low"),
+        ]);
+        match bands {
+            Err(DiceError::OutcomeOverlap {
+                first,
+                second,
+                value,
+            }) => {
+                assert_eq!(first, "Low");
+                assert_eq!(second, "High");
+                assert_eq!(value, 5);
+            }
+            other => panic!("Expected an overlap error, got {:?}", other),
+        }
+    }
+}
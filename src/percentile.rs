@@ -0,0 +1,290 @@
+use rand::Rng;
+use std::cmp::Ordering;
+
+/// The highest total, rolled as `00` on the tens dice and `0` on the units dice.
+const MAXIMUM: u32 = 100;
+/// The most bonus or penalty dice which can be rolled, after they cancel, as in Call of Cthulhu.
+pub const MAX_EXTRA_TENS_DICE: u32 = 2;
+
+/// Represents how well a percentile roll succeeded against a skill value.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum SuccessLevel {
+    /// A roll of 100, or 96 and above against a skill below 50.
+    Fumble,
+    /// A roll above the skill value.
+    Failure,
+    /// A roll equal to or below the skill value.
+    Regular,
+    /// A roll equal to or below half the skill value.
+    Hard,
+    /// A roll equal to or below a fifth of the skill value.
+    Extreme,
+    /// A roll of 01.
+    Critical,
+}
+
+/// Represents a d100 rolled as a tens dice and a units dice, with bonus or penalty dice.
+/// Each bonus or penalty dice is an extra tens dice, keeping the best or worst. Bonus and penalty dice cancel one for one.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct PercentileRoll {
+    /// The number of bonus dice.
+    pub bonus_dice: u32,
+    /// The number of penalty dice.
+    pub penalty_dice: u32,
+}
+
+impl Default for PercentileRoll {
+    fn default() -> Self {
+        Self::new(0, 0)
+    }
+}
+
+impl PercentileRoll {
+    /// Creates a new `PercentileRoll`.
+    /// Bonus or penalty dice left over after cancelling beyond `MAX_EXTRA_TENS_DICE` are dropped.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::percentile::PercentileRoll;
+    ///
+    /// // Two bonus dice and one penalty dice leaves a single bonus dice
+    /// let roll = PercentileRoll::new(2, 1);
+    /// assert_eq!(roll.extra_tens_dice(), 1);
+    ///
+    /// // Five bonus dice are capped at two
+    /// let roll = PercentileRoll::new(5, 0);
+    /// assert_eq!(roll.bonus_dice, 2);
+    /// ```
+    #[must_use]
+    pub fn new(bonus_dice: u32, penalty_dice: u32) -> Self {
+        PercentileRoll {
+            bonus_dice: bonus_dice.min(penalty_dice.saturating_add(MAX_EXTRA_TENS_DICE)),
+            penalty_dice: penalty_dice.min(bonus_dice.saturating_add(MAX_EXTRA_TENS_DICE)),
+        }
+    }
+
+    /// The number of extra tens dice after bonus and penalty dice cancel. Positive for bonus dice and negative for penalty dice.
+    /// At most `MAX_EXTRA_TENS_DICE` either way, including for a `PercentileRoll` built without `new`.
+    #[must_use]
+    pub fn extra_tens_dice(&self) -> i64 {
+        let limit = i64::from(MAX_EXTRA_TENS_DICE);
+        (i64::from(self.bonus_dice) - i64::from(self.penalty_dice)).clamp(-limit, limit)
+    }
+
+    /// Rolls the percentile dice. Using underlying OS RNG for the dice roll.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::percentile::PercentileRoll;
+    ///
+    /// let result = PercentileRoll::new(1, 0).roll_percentile();
+    /// assert!((1..=100).contains(&result.result));
+    /// ```
+    #[must_use]
+    pub fn roll_percentile(&self) -> PercentileResult {
+        let mut rng = rand::thread_rng();
+        self.roll_percentile_from_rng(&mut rng)
+    }
+
+    /// Rolls the percentile dice. Uses a source of RNG passed in. Useful for testing.
+    /// The units dice is rolled first, followed by the tens dice.
+    ///
+    /// # Examples
+    /// ```
+    /// use rand::SeedableRng;
+    /// use dnd_dice_roller::percentile::PercentileRoll;
+    ///
+    /// let rng = rand_pcg::Pcg64Mcg::seed_from_u64(42);
+    /// let result = PercentileRoll::new(0, 2).roll_percentile_from_rng(rng);
+    /// assert_eq!(result.tens.len(), 3);
+    /// ```
+    /// # Panics
+    /// Will not panic, at least one tens dice is always rolled.
+    pub fn roll_percentile_from_rng<R: Rng + Sized>(&self, mut rng: R) -> PercentileResult {
+        let units = rng.gen_range(0..10);
+        let tens: Vec<u32> = (0..=self.extra_tens_dice().unsigned_abs())
+            .map(|_| rng.gen_range(0..10) * 10)
+            .collect();
+
+        let totals = tens.iter().map(|ten| total(*ten, units));
+        let result = match self.extra_tens_dice().cmp(&0) {
+            Ordering::Less => totals.max(),
+            _ => totals.min(),
+        }
+        .expect("At least one tens dice is rolled");
+
+        PercentileResult {
+            tens,
+            units,
+            result,
+        }
+    }
+}
+
+/// Combines a tens dice and a units dice, where `00` and `0` is 100.
+fn total(tens: u32, units: u32) -> u32 {
+    match tens + units {
+        0 => MAXIMUM,
+        result => result,
+    }
+}
+
+/// Represents the result of a `PercentileRoll`.
+#[derive(Clone, PartialEq, Debug)]
+pub struct PercentileResult {
+    /// The tens dice rolled, as multiples of ten from 0 to 90. More than one when bonus or penalty dice were rolled.
+    pub tens: Vec<u32>,
+    /// The units dice rolled, from 0 to 9.
+    pub units: u32,
+    /// The result from 1 to 100, using the best tens dice for bonus dice or the worst for penalty dice.
+    pub result: u32,
+}
+
+impl PercentileResult {
+    /// How well the roll succeeded against a skill value.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::percentile::{PercentileResult, SuccessLevel};
+    ///
+    /// let result = PercentileResult { tens: vec![20], units: 5, result: 25 };
+    /// assert_eq!(result.success_level(60), SuccessLevel::Hard);
+    /// assert_eq!(result.success_level(20), SuccessLevel::Failure);
+    /// ```
+    #[must_use]
+    pub fn success_level(&self, skill: u32) -> SuccessLevel {
+        let fumble_threshold = if skill < 50 { 96 } else { MAXIMUM };
+        match self.result {
+            1 => SuccessLevel::Critical,
+            result if result >= fumble_threshold => SuccessLevel::Fumble,
+            result if result <= skill / 5 => SuccessLevel::Extreme,
+            result if result <= skill / 2 => SuccessLevel::Hard,
+            result if result <= skill => SuccessLevel::Regular,
+            _ => SuccessLevel::Failure,
+        }
+    }
+
+    /// Whether the roll succeeded at the difficulty given, e.g. `SuccessLevel::Hard` for a hard roll.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::percentile::{PercentileResult, SuccessLevel};
+    ///
+    /// let result = PercentileResult { tens: vec![20], units: 5, result: 25 };
+    /// assert!(result.succeeds(60, SuccessLevel::Regular));
+    /// assert!(!result.succeeds(60, SuccessLevel::Extreme));
+    /// ```
+    #[must_use]
+    pub fn succeeds(&self, skill: u32, difficulty: SuccessLevel) -> bool {
+        self.success_level(skill) >= difficulty
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use rand::SeedableRng;
+
+    const SEED: u64 = 42;
+
+    fn result(value: u32) -> PercentileResult {
+        PercentileResult {
+            tens: vec![value / 10 * 10],
+            units: value % 10,
+            result: value,
+        }
+    }
+
+    #[test]
+    fn zero_zero_is_one_hundred() {
+        assert_eq!(total(0, 0), 100);
+        assert_eq!(total(0, 5), 5);
+        assert_eq!(total(90, 0), 90);
+    }
+
+    #[test]
+    fn produces_predictable_results() {
+        let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let result = PercentileRoll::default().roll_percentile_from_rng(rng);
+        assert_eq!(result.tens, vec![90]);
+        assert_eq!(result.units, 2);
+        assert_eq!(result.result, 92);
+    }
+
+    #[test]
+    fn bonus_dice_keep_the_lowest_result() {
+        let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let result = PercentileRoll::new(2, 0).roll_percentile_from_rng(rng);
+        assert_eq!(result.tens, vec![90, 10, 60]);
+        assert_eq!(result.units, 2);
+        assert_eq!(result.result, 12);
+    }
+
+    #[test]
+    fn penalty_dice_keep_the_highest_result() {
+        let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let result = PercentileRoll::new(1, 3).roll_percentile_from_rng(rng);
+        assert_eq!(result.tens, vec![90, 10, 60]);
+        assert_eq!(result.units, 2);
+        assert_eq!(result.result, 92);
+    }
+
+    #[test]
+    fn bonus_and_penalty_dice_cancel() {
+        let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let result = PercentileRoll::new(1, 1).roll_percentile_from_rng(rng);
+        assert_eq!(result.tens, vec![90]);
+        assert_eq!(result.units, 2);
+        assert_eq!(result.result, 92);
+    }
+
+    #[test]
+    fn extra_tens_dice_do_not_wrap() {
+        assert_eq!(PercentileRoll::new(u32::MAX, 0).extra_tens_dice(), 2);
+        assert_eq!(PercentileRoll::new(0, u32::MAX).extra_tens_dice(), -2);
+        assert_eq!(PercentileRoll::new(u32::MAX, u32::MAX).extra_tens_dice(), 0);
+    }
+
+    #[test]
+    fn huge_bonus_and_penalty_counts_are_capped() {
+        let roll = PercentileRoll::new(u32::MAX, 1);
+        assert_eq!((roll.bonus_dice, roll.penalty_dice), (3, 1));
+        let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        assert_eq!(roll.roll_percentile_from_rng(rng).tens, vec![90, 10, 60]);
+
+        let roll = PercentileRoll {
+            bonus_dice: 0,
+            penalty_dice: u32::MAX,
+        };
+        let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let result = roll.roll_percentile_from_rng(rng);
+        assert_eq!(result.tens, vec![90, 10, 60]);
+        assert_eq!(result.result, 92);
+    }
+
+    #[test]
+    fn success_levels() {
+        assert_eq!(result(1).success_level(50), SuccessLevel::Critical);
+        assert_eq!(result(10).success_level(50), SuccessLevel::Extreme);
+        assert_eq!(result(11).success_level(50), SuccessLevel::Hard);
+        assert_eq!(result(25).success_level(50), SuccessLevel::Hard);
+        assert_eq!(result(50).success_level(50), SuccessLevel::Regular);
+        assert_eq!(result(51).success_level(50), SuccessLevel::Failure);
+        assert_eq!(result(99).success_level(50), SuccessLevel::Failure);
+        assert_eq!(result(100).success_level(50), SuccessLevel::Fumble);
+    }
+
+    #[test]
+    fn low_skills_fumble_from_ninety_six() {
+        assert_eq!(result(95).success_level(40), SuccessLevel::Failure);
+        assert_eq!(result(96).success_level(40), SuccessLevel::Fumble);
+    }
+
+    #[test]
+    fn higher_success_levels_pass_easier_difficulties() {
+        assert!(result(10).succeeds(50, SuccessLevel::Hard));
+        assert!(!result(30).succeeds(50, SuccessLevel::Hard));
+        assert!(!result(100).succeeds(50, SuccessLevel::Regular));
+    }
+}
@@ -0,0 +1,177 @@
+use crate::{dice_result::DiceSetResults, die_roller::DieRoller, error::DiceError, roll::Roll};
+
+use alloc::vec::Vec;
+
+/// Represents real dice rolled by a player, with each face entered through a prompt.
+///
+/// Faces are asked for in the same order a random roll would roll them, including the second set of dice for
+/// advantage and disadvantage, rerolls and extra dice for exploding dice.
+/// For fudge and custom dice the face is the 1-based position of the face on the dice.
+pub struct PhysicalDice<F: FnMut(u32) -> u32> {
+    prompt: F,
+    error: Option<DiceError>,
+}
+
+impl<F: FnMut(u32) -> u32> PhysicalDice<F> {
+    /// Creates a new `PhysicalDice` which calls the prompt with the sides of each dice to be rolled, and uses the face it returns.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::str::FromStr;
+    /// use dnd_dice_roller::physical::PhysicalDice;
+    /// use dnd_dice_roller::roll::Roll;
+    /// # use dnd_dice_roller::error::DiceError;
+    ///
+    /// let roll = Roll::from_str("d20 + 3 a")?;
+    /// let mut entered = vec![17, 4].into_iter();
+    /// let results = PhysicalDice::new(|_sides| entered.next().unwrap_or(1)).resolve(&roll)?;
+    /// assert_eq!(results[0].final_result, 20);
+    /// # Ok::<(), DiceError>(())
+    /// ```
+    #[must_use]
+    pub fn new(prompt: F) -> Self {
+        PhysicalDice {
+            prompt,
+            error: None,
+        }
+    }
+
+    /// Resolves the roll using the faces entered.
+    ///
+    /// # Errors
+    /// Errors with `DiceError::InvalidFace` if a face entered cannot be rolled on its dice. No more faces are asked for after an invalid face.
+    pub fn resolve(mut self, roll: &Roll) -> Result<Vec<DiceSetResults>, DiceError> {
+        let results = roll.roll_with(&mut self);
+        match self.error {
+            Some(error) => Err(error),
+            None => Ok(results),
+        }
+    }
+}
+
+impl<F: FnMut(u32) -> u32> DieRoller for PhysicalDice<F> {
+    fn roll_die(&mut self, sides: u32) -> u32 {
+        // Any face will do once an error has occurred as the results are discarded
+        if self.error.is_some() {
+            return 1;
+        }
+        let face = (self.prompt)(sides);
+        if face == 0 || face > sides {
+            self.error = Some(DiceError::InvalidFace { face, sides });
+            return 1;
+        }
+        face
+    }
+}
+
+/// Resolves a roll using a list of faces rolled on real dice, in the order `PhysicalDice` would ask for them.
+///
+/// # Examples
+/// ```
+/// use std::str::FromStr;
+/// use dnd_dice_roller::physical::resolve_faces;
+/// use dnd_dice_roller::roll::Roll;
+/// # use dnd_dice_roller::error::DiceError;
+///
+/// let roll = Roll::from_str("2d6 + 1, d20 d")?;
+/// let results = resolve_faces(&roll, &[3, 5, 12, 9])?;
+/// assert_eq!(results[0].final_result, 9);
+/// assert_eq!(results[1].final_result, 9);
+/// # Ok::<(), DiceError>(())
+/// ```
+/// # Errors
+/// Errors with `DiceError::InvalidFace` if a face cannot be rolled on its dice,
+/// `DiceError::MissingFaces` if there are too few faces, or `DiceError::UnusedFaces` if there are too many.
+pub fn resolve_faces(roll: &Roll, faces: &[u32]) -> Result<Vec<DiceSetResults>, DiceError> {
+    let mut remaining = faces.iter();
+    let mut missing = false;
+    let results = PhysicalDice::new(|_| {
+        remaining.next().copied().unwrap_or_else(|| {
+            missing = true;
+            1
+        })
+    })
+    .resolve(roll)?;
+
+    if missing {
+        return Err(DiceError::MissingFaces(faces.len()));
+    }
+    match remaining.len() {
+        0 => Ok(results),
+        unused => Err(DiceError::UnusedFaces(unused)),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::scripted_rng::ScriptedRng;
+    use std::str::FromStr;
+
+    fn roll(input: &str) -> Roll {
+        Roll::from_str(input).expect("Valid roll")
+    }
+
+    #[test]
+    fn matches_a_random_roll_with_the_same_faces() {
+        let roll = roll("3d6 + 2 a, 2d8 - 1 d");
+        let faces = [2, 6, 5, 1, 1, 6, 8, 3, 4, 4];
+        let expected = roll.roll_from_rng(ScriptedRng::new(6, &faces[..6]).then(8, &faces[6..]));
+        assert_eq!(resolve_faces(&roll, &faces).expect("Valid faces"), expected);
+    }
+
+    #[test]
+    fn prompts_with_the_sides_of_each_dice() {
+        let mut prompted = Vec::new();
+        let results = PhysicalDice::new(|sides| {
+            prompted.push(sides);
+            sides
+        })
+        .resolve(&roll("d20 + d4, d6"))
+        .expect("Valid faces");
+        assert_eq!(prompted, vec![20, 4, 6]);
+        assert_eq!(results[0].final_result, 24);
+    }
+
+    #[test]
+    fn prompts_for_exploding_dice() {
+        let mut entered = vec![10, 10, 3, 7, 2].into_iter();
+        let results = PhysicalDice::new(|_| entered.next().expect("Enough faces"))
+            .resolve(&roll("3k2"))
+            .expect("Valid faces");
+        assert_eq!(results[0].dice_results[0].first_roll, vec![23, 7, 2]);
+        assert_eq!(results[0].final_result, 30);
+    }
+
+    #[test]
+    fn rejects_faces_the_dice_cannot_roll() {
+        let mut prompts = 0;
+        let result = PhysicalDice::new(|_| {
+            prompts += 1;
+            7
+        })
+        .resolve(&roll("2d6"));
+        assert!(matches!(
+            result,
+            Err(DiceError::InvalidFace { face: 7, sides: 6 })
+        ));
+        assert_eq!(prompts, 1);
+        assert!(matches!(
+            resolve_faces(&roll("d20"), &[0]),
+            Err(DiceError::InvalidFace { face: 0, sides: 20 })
+        ));
+    }
+
+    #[test]
+    fn requires_every_face_to_be_used() {
+        assert!(matches!(
+            resolve_faces(&roll("3d6"), &[1, 2]),
+            Err(DiceError::MissingFaces(2))
+        ));
+        assert!(matches!(
+            resolve_faces(&roll("3d6"), &[1, 2, 3, 4]),
+            Err(DiceError::UnusedFaces(1))
+        ));
+    }
+}
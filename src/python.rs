@@ -0,0 +1,518 @@
+use crate::{
+    dice::{Dice as DiceInner, Operation, RollType},
+    dice_result::{DiceSetResults as DiceSetResultsInner, RollResult as RollResultInner},
+    dice_set::DiceSet as DiceSetInner,
+    die_roller::DieRoller,
+    distribution::Distribution as DistributionInner,
+    roll::Roll as RollInner,
+};
+
+use pyo3::create_exception;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use rand::SeedableRng;
+use std::str::FromStr;
+
+create_exception!(
+    dnd_dice_roller,
+    DiceError,
+    PyValueError,
+    "Raised when a dice input string or dice cannot be used."
+);
+
+fn to_py_error(error: &crate::error::DiceError) -> PyErr {
+    DiceError::new_err(error.to_string())
+}
+
+fn repr_string(value: &str) -> String {
+    format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'"))
+}
+
+/// Rolls with the OS RNG, or a `Pcg64Mcg` RNG created from the seed given.
+fn roll_with_seed<T>(seed: Option<u64>, roll: impl FnOnce(&mut dyn DieRoller) -> T) -> T {
+    match seed {
+        Some(seed) => roll(&mut rand_pcg::Pcg64Mcg::seed_from_u64(seed)),
+        None => roll(&mut rand::thread_rng()),
+    }
+}
+
+/// A dice input string e.g. `Roll("2d20 + 1 a, 3d6")`, with one result per comma separated group.
+#[pyclass(name = "Roll", module = "dnd_dice_roller", frozen)]
+pub struct PyRoll {
+    roll: RollInner,
+}
+
+#[pymethods]
+impl PyRoll {
+    #[new]
+    fn new(notation: &str) -> PyResult<Self> {
+        let roll = RollInner::from_str(notation).map_err(|error| to_py_error(&error))?;
+        Ok(PyRoll { roll })
+    }
+
+    /// The groups of dice, each rolled for a separate result.
+    #[getter]
+    fn dice_sets(&self) -> Vec<PyDiceSet> {
+        self.roll
+            .dice_sets()
+            .iter()
+            .map(|dice_set| PyDiceSet {
+                dice: dice_set.dice().to_vec(),
+            })
+            .collect()
+    }
+
+    /// Rolls every group of dice, using a seeded RNG when a seed is given so the results are repeatable.
+    #[pyo3(signature = (seed = None))]
+    fn roll(&self, seed: Option<u64>) -> Vec<PyDiceSetResults> {
+        roll_with_seed(seed, |roller| self.roll.roll_with(roller))
+            .into_iter()
+            .map(PyDiceSetResults::from)
+            .collect()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Roll({})", repr_string(&self.roll.to_string()))
+    }
+
+    fn __str__(&self) -> String {
+        self.roll.to_string()
+    }
+}
+
+/// A group of dice rolled for a single result e.g. `d20 + 2 + d4`.
+#[pyclass(name = "DiceSet", module = "dnd_dice_roller", frozen)]
+pub struct PyDiceSet {
+    dice: Vec<DiceInner>,
+}
+
+impl PyDiceSet {
+    fn dice_set(&self) -> DiceSetInner {
+        DiceSetInner::new(self.dice.clone())
+    }
+}
+
+#[pymethods]
+impl PyDiceSet {
+    /// The dice in the group, each added to or subtracted from the result.
+    #[getter]
+    fn dice(&self) -> Vec<PyDice> {
+        self.dice
+            .iter()
+            .cloned()
+            .map(|dice| PyDice { dice })
+            .collect()
+    }
+
+    /// Rolls the group, using a seeded RNG when a seed is given so the results are repeatable.
+    #[pyo3(signature = (seed = None))]
+    fn roll(&self, seed: Option<u64>) -> PyDiceSetResults {
+        let dice_set = self.dice_set();
+        roll_with_seed(seed, |roller| dice_set.roll_dice_set_with(roller)).into()
+    }
+
+    /// The exact probability of every result the group can roll.
+    fn distribution(&self) -> PyDistribution {
+        self.dice_set().distribution().into()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("DiceSet({})", repr_string(&self.dice_set().to_string()))
+    }
+
+    fn __str__(&self) -> String {
+        self.dice_set().to_string()
+    }
+}
+
+/// A number of dice with the same sides e.g. `Dice(2, 20, modifier=1, roll_type="advantage")`.
+#[pyclass(name = "Dice", module = "dnd_dice_roller", frozen, eq)]
+#[derive(PartialEq)]
+pub struct PyDice {
+    dice: DiceInner,
+}
+
+#[pymethods]
+impl PyDice {
+    /// `roll_type` is one of `"regular"`, `"advantage"` or `"disadvantage"`.
+    #[new]
+    #[pyo3(signature = (number_of_dice, sides, modifier = None, roll_type = "regular"))]
+    fn new(
+        number_of_dice: u32,
+        sides: u32,
+        modifier: Option<i32>,
+        roll_type: &str,
+    ) -> PyResult<Self> {
+        let roll_type = match roll_type {
+            "regular" => RollType::Regular,
+            "advantage" => RollType::Advantage,
+            "disadvantage" => RollType::Disadvantage,
+            _ => {
+                return Err(DiceError::new_err(format!(
+                    "Unknown roll type {roll_type}, expected regular, advantage or disadvantage"
+                )))
+            }
+        };
+        if sides == 0 {
+            return Err(DiceError::new_err("Dice must have at least one side"));
+        }
+        Ok(PyDice {
+            dice: DiceInner::new(
+                number_of_dice,
+                sides,
+                modifier,
+                roll_type,
+                Operation::Addition,
+            ),
+        })
+    }
+
+    #[getter]
+    fn number_of_dice(&self) -> u32 {
+        self.dice.number_of_dice_to_roll
+    }
+
+    #[getter]
+    fn sides(&self) -> u32 {
+        self.dice.sides
+    }
+
+    #[getter]
+    fn modifier(&self) -> Option<i32> {
+        self.dice.modifier
+    }
+
+    /// One of `"regular"`, `"advantage"` or `"disadvantage"`.
+    #[getter]
+    fn roll_type(&self) -> &'static str {
+        match self.dice.roll_type {
+            RollType::Regular => "regular",
+            RollType::Advantage => "advantage",
+            RollType::Disadvantage => "disadvantage",
+        }
+    }
+
+    /// Whether the dice is subtracted from the result of its group.
+    #[getter]
+    fn subtracted(&self) -> bool {
+        self.dice.operation == Operation::Subtraction
+    }
+
+    /// A copy of the dice which rolls again and adds whenever it rolls its highest face.
+    fn exploding(&self) -> Self {
+        PyDice {
+            dice: self.dice.clone().exploding(),
+        }
+    }
+
+    /// A copy of the dice where only the highest `keep` dice count.
+    fn keep_highest(&self, keep: u32) -> Self {
+        PyDice {
+            dice: self.dice.clone().keep_highest(keep),
+        }
+    }
+
+    /// A copy of the dice where only the lowest `keep` dice count.
+    fn keep_lowest(&self, keep: u32) -> Self {
+        PyDice {
+            dice: self.dice.clone().keep_lowest(keep),
+        }
+    }
+
+    /// A copy of the dice which rerolls `face` or lower once.
+    fn rerolling(&self, face: u32) -> Self {
+        PyDice {
+            dice: self.dice.clone().rerolling(face),
+        }
+    }
+
+    /// Rolls the dice, using a seeded RNG when a seed is given so the results are repeatable.
+    #[pyo3(signature = (seed = None))]
+    fn roll(&self, seed: Option<u64>) -> PyRollResult {
+        roll_with_seed(seed, |roller| self.dice.roll_dice_with(roller)).into()
+    }
+
+    /// The exact probability of every result the dice can roll.
+    fn distribution(&self) -> PyDistribution {
+        self.dice.distribution().into()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Dice({})", repr_string(&self.dice.to_string()))
+    }
+
+    fn __str__(&self) -> String {
+        self.dice.to_string()
+    }
+}
+
+/// The result of rolling a group of dice.
+#[pyclass(name = "DiceSetResults", module = "dnd_dice_roller", frozen, eq)]
+#[derive(PartialEq)]
+pub struct PyDiceSetResults {
+    results: DiceSetResultsInner,
+}
+
+impl From<DiceSetResultsInner> for PyDiceSetResults {
+    fn from(results: DiceSetResultsInner) -> Self {
+        PyDiceSetResults { results }
+    }
+}
+
+#[pymethods]
+impl PyDiceSetResults {
+    #[getter]
+    fn final_result(&self) -> i32 {
+        self.results.final_result
+    }
+
+    /// The result of each dice in the group.
+    #[getter]
+    fn dice_results(&self) -> Vec<PyRollResult> {
+        self.results
+            .dice_results
+            .iter()
+            .cloned()
+            .map(PyRollResult::from)
+            .collect()
+    }
+
+    fn __int__(&self) -> i32 {
+        self.results.final_result
+    }
+
+    fn __repr__(&self) -> String {
+        let dice_results: Vec<String> = self
+            .dice_results()
+            .iter()
+            .map(PyRollResult::__repr__)
+            .collect();
+        format!(
+            "DiceSetResults(final_result={}, dice_results=[{}])",
+            self.results.final_result,
+            dice_results.join(", ")
+        )
+    }
+}
+
+/// The result of rolling a single `Dice`, with the faces of the second roll for advantage and disadvantage.
+#[pyclass(name = "RollResult", module = "dnd_dice_roller", frozen, eq)]
+#[derive(PartialEq)]
+pub struct PyRollResult {
+    result: RollResultInner,
+}
+
+impl From<RollResultInner> for PyRollResult {
+    fn from(result: RollResultInner) -> Self {
+        PyRollResult { result }
+    }
+}
+
+#[pymethods]
+impl PyRollResult {
+    #[getter]
+    fn first_roll(&self) -> Vec<u32> {
+        self.result.first_roll.clone()
+    }
+
+    #[getter]
+    fn second_roll(&self) -> Option<Vec<u32>> {
+        self.result.second_roll.clone()
+    }
+
+    #[getter]
+    fn result(&self) -> i32 {
+        self.result.result
+    }
+
+    fn __int__(&self) -> i32 {
+        self.result.result
+    }
+
+    fn __str__(&self) -> String {
+        self.result.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        let second_roll = self
+            .result
+            .second_roll
+            .as_ref()
+            .map_or_else(|| String::from("None"), |roll| format!("{roll:?}"));
+        format!(
+            "RollResult(result={}, first_roll={:?}, second_roll={second_roll})",
+            self.result.result, self.result.first_roll
+        )
+    }
+}
+
+/// The exact probability of every result a roll can produce.
+#[pyclass(name = "Distribution", module = "dnd_dice_roller", frozen)]
+pub struct PyDistribution {
+    distribution: DistributionInner,
+}
+
+impl From<DistributionInner> for PyDistribution {
+    fn from(distribution: DistributionInner) -> Self {
+        PyDistribution { distribution }
+    }
+}
+
+#[pymethods]
+impl PyDistribution {
+    /// The probability of rolling exactly `value`.
+    fn probability(&self, value: i32) -> f64 {
+        self.distribution.probability(value)
+    }
+
+    /// The probability of rolling at least `start` and less than `stop`, like a Python `range`.
+    /// Either bound may be left out e.g. `probability_in_range(15)` is the chance to meet or beat 15.
+    #[pyo3(signature = (start = None, stop = None))]
+    fn probability_in_range(&self, start: Option<i32>, stop: Option<i32>) -> f64 {
+        match (start, stop) {
+            (Some(start), Some(stop)) => self.distribution.probability_in_range(start..stop),
+            (Some(start), None) => self.distribution.probability_in_range(start..),
+            (None, Some(stop)) => self.distribution.probability_in_range(..stop),
+            (None, None) => self.distribution.probability_in_range(..),
+        }
+    }
+
+    #[getter]
+    fn mean(&self) -> f64 {
+        self.distribution.mean()
+    }
+
+    #[getter]
+    fn min(&self) -> Option<i32> {
+        self.distribution.min()
+    }
+
+    #[getter]
+    fn max(&self) -> Option<i32> {
+        self.distribution.max()
+    }
+
+    /// Each possible result and its probability as `(result, probability)` pairs, from lowest to highest result.
+    fn items(&self) -> Vec<(i32, f64)> {
+        self.distribution.iter().collect()
+    }
+
+    fn __len__(&self) -> usize {
+        self.distribution.iter().count()
+    }
+
+    fn __repr__(&self) -> String {
+        match (self.distribution.min(), self.distribution.max()) {
+            (Some(min), Some(max)) => format!(
+                "Distribution(min={min}, max={max}, mean={})",
+                self.distribution.mean()
+            ),
+            _ => String::from("Distribution()"),
+        }
+    }
+}
+
+/// Parses and rolls a dice input string in one step, using a seeded RNG when a seed is given so the results are repeatable.
+#[pyfunction]
+#[pyo3(signature = (notation, seed = None))]
+fn roll(notation: &str, seed: Option<u64>) -> PyResult<Vec<PyDiceSetResults>> {
+    Ok(PyRoll::new(notation)?.roll(seed))
+}
+
+/// The `dnd_dice_roller` Python module.
+///
+/// # Errors
+/// Errors if a class or function cannot be added to the module.
+#[pymodule]
+pub fn dnd_dice_roller(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add("DiceError", module.py().get_type::<DiceError>())?;
+    module.add_class::<PyRoll>()?;
+    module.add_class::<PyDiceSet>()?;
+    module.add_class::<PyDice>()?;
+    module.add_class::<PyDiceSetResults>()?;
+    module.add_class::<PyRollResult>()?;
+    module.add_class::<PyDistribution>()?;
+    module.add_function(wrap_pyfunction!(roll, module)?)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use std::ffi::CString;
+    use std::sync::Once;
+
+    static INITIALIZE: Once = Once::new();
+
+    // Runs Python code with the module imported as `dice`, failing the test on any exception e.g. a failed `assert`
+    fn run(code: &str) {
+        INITIALIZE.call_once(|| {
+            pyo3::append_to_inittab!(dnd_dice_roller);
+            pyo3::prepare_freethreaded_python();
+        });
+        let code =
+            CString::new(format!("import dnd_dice_roller as dice\n{code}")).expect("No nul bytes");
+        Python::with_gil(|py| {
+            if let Err(error) = py.run(&code, None, None) {
+                error.print(py);
+                panic!("Python code raised {}", error);
+            }
+        });
+    }
+
+    #[test]
+    fn rolls_seeded_dice() {
+        run(r#"
+results = dice.Roll("3d6 + 1, d20 a").roll(seed=42)
+assert [int(result) for result in results] == [14, results[1].final_result]
+assert results[0].dice_results[0].first_roll == [2, 6, 5]
+assert results[0].dice_results[0].second_roll is None
+assert len(results[1].dice_results[0].second_roll) == 1
+assert results == dice.roll("3d6 + 1, d20 a", 42)
+assert dice.Dice(3, 6, modifier=1).roll(seed=42).result == 14
+assert 1 <= dice.Roll("d20").roll()[0].final_result <= 20
+"#);
+    }
+
+    #[test]
+    fn reprs_use_dice_notation() {
+        run(r#"
+roll = dice.Roll("3d6+1,d20 - d4 a")
+assert repr(roll) == "Roll('3d6 + 1, d20 - d4 a')", repr(roll)
+assert str(roll) == "3d6 + 1, d20 - d4 a"
+assert repr(roll.dice_sets[1]) == "DiceSet('d20 - d4 a')"
+assert roll.dice_sets[1].dice[1].subtracted
+assert repr(dice.Dice(4, 6).keep_highest(3)) == "Dice('4d6kh3')"
+assert repr(dice.Dice(1, 20, -1, "disadvantage")) == "Dice('d20 - 1 d')"
+assert repr(dice.Dice(2, 6).roll(seed=42)) == "RollResult(result=8, first_roll=[2, 6], second_roll=None)"
+"#);
+    }
+
+    #[test]
+    fn exposes_distributions() {
+        run(r#"
+distribution = dice.Dice(1, 20, modifier=5).distribution()
+assert abs(distribution.probability_in_range(15) - 0.55) < 1e-9
+assert abs(distribution.mean - 15.5) < 1e-9
+assert (distribution.min, distribution.max, len(distribution)) == (6, 25, 20)
+two_d6 = dice.Roll("2d6").dice_sets[0].distribution()
+assert abs(two_d6.probability(7) - 6 / 36) < 1e-9
+assert abs(two_d6.probability_in_range(2, 4) - 3 / 36) < 1e-9
+assert abs(sum(probability for _, probability in two_d6.items()) - 1) < 1e-9
+"#);
+    }
+
+    #[test]
+    fn raises_dice_errors() {
+        run(r#"
+for bad in [lambda: dice.Roll("2d"), lambda: dice.Dice(1, 6, roll_type="sideways"), lambda: dice.Dice(1, 0)]:
+    try:
+        bad()
+    except dice.DiceError as error:
+        assert isinstance(error, ValueError)
+    else:
+        raise AssertionError("Expected DiceError")
+"#);
+    }
+}
@@ -1,9 +1,12 @@
-use std::str::FromStr;
-
-use dice_command_parser::parse_line;
+use alloc::vec::Vec;
+use core::fmt;
+use core::str::FromStr;
 use rand::Rng;
 
-use crate::{dice::Dice, dice_result::DiceSetResults, dice_set::DiceSet, error::DiceError};
+use crate::{
+    dice::NamedDice, dice_result::DiceSetResults, dice_set::DiceSet, die_roller::DieRoller,
+    error::DiceError, notation::parse_dice_sets,
+};
 /// Represents a set of non-homogenous dice, potentially grouped into multiple separate results - each grouping being a `DiceSet`.
 ///  e.g. Rolling a d6 + d4 would be a `Roll` of a single `DiceSet`.
 ///  e.g. Rolling a d100, d100, d100 for three separate results (e.g. three rolls on a loot table) would be a single `Roll` of three `DiceSet` each of one `Dice`.
@@ -14,10 +17,37 @@ pub struct Roll {
 
 impl Roll {
     /// Creates a new `Roll`
+    #[must_use]
     pub fn new(dice_sets: Vec<DiceSet>) -> Self {
         Self { dice_sets }
     }
 
+    /// The sets of dice rolled, each producing a separate result.
+    #[must_use]
+    pub fn dice_sets(&self) -> &[DiceSet] {
+        &self.dice_sets
+    }
+
+    /// Creates a `Roll` from an input string which may refer to custom dice by name e.g. `2d[averaging] + 1`.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::dice::{Face, NamedDice};
+    /// use dnd_dice_roller::roll::Roll;
+    /// # use dnd_dice_roller::error::DiceError;
+    ///
+    /// let mut named_dice = NamedDice::new();
+    /// let faces = [2, 3, 3, 4, 4, 5].iter().map(|value| Face::Number(*value)).collect();
+    /// named_dice.insert("averaging", faces)?;
+    /// let roll = Roll::parse_with_named_dice("2d[averaging] + 1", &named_dice)?;
+    /// # Ok::<(), DiceError>(())
+    /// ```
+    /// # Errors
+    /// Errors can occur if the dice input string is in the wrong format or refers to an unknown dice `DiceError::ParseError`.
+    pub fn parse_with_named_dice(input: &str, named_dice: &NamedDice) -> Result<Self, DiceError> {
+        Ok(Self::new(parse_dice_sets(input, named_dice)?))
+    }
+
     /// Rolls one more sets of dice and produces a `Vec<DiceSetResults>`. Using underlying OS RNG for the dice roll.
     ///
     /// # Examples
@@ -30,7 +60,7 @@ impl Roll {
     /// let result = roll.roll();
     /// # Ok::<(), DiceError>(())
     /// ```
-
+    ///
     /// ```
     /// use std::str::FromStr;
     /// use dnd_dice_roller::roll::Roll;
@@ -40,6 +70,7 @@ impl Roll {
     /// let result = roll.roll();
     /// # Ok::<(), DiceError>(())
     /// ```
+    #[cfg(feature = "std")]
     #[must_use]
     pub fn roll(&self) -> Vec<DiceSetResults> {
         let mut rng = rand::thread_rng();
@@ -61,13 +92,46 @@ impl Roll {
     /// # Ok::<(), DiceError>(())
     /// ```
     pub fn roll_from_rng<R: Rng + Sized>(&self, mut rng: R) -> Vec<DiceSetResults> {
+        self.roll_with(&mut rng)
+    }
+
+    /// Rolls one more sets of and produces `Vec<DiceSetResults>`. Uses a `DieRoller` passed in, which can be chosen at runtime.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::str::FromStr;
+    /// use dnd_dice_roller::roll::Roll;
+    /// use dnd_dice_roller::scripted_rng::ScriptedRng;
+    /// # use dnd_dice_roller::error::DiceError;
+    ///
+    /// let mut roller = ScriptedRng::new(6, &[6, 6, 6]);
+    /// let roll = Roll::from_str("3d6 + 1")?;
+    /// let result = roll.roll_with(&mut roller);
+    /// assert_eq!(result[0].final_result, 19);
+    /// # Ok::<(), DiceError>(())
+    /// ```
+    pub fn roll_with(&self, roller: &mut dyn DieRoller) -> Vec<DiceSetResults> {
         self.dice_sets
             .iter()
-            .map(|d| d.roll_dice_set_from_rng(&mut rng))
+            .map(|d| d.roll_dice_set_with(roller))
             .collect()
     }
 }
 
+/// Formats the roll in dice notation, with the dice sets separated by commas e.g. `d100, 3d6 + 2`.
+/// Parsing the notation produces the same roll, other than for custom dice referred to by name which are written out in full.
+impl fmt::Display for Roll {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, dice_set) in self.dice_sets.iter().enumerate() {
+            if index > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{dice_set}")?;
+        }
+        Ok(())
+    }
+}
+
 impl FromStr for Roll {
     type Err = DiceError;
     /// Creates a `Roll` from an input string.
@@ -116,20 +180,46 @@ impl FromStr for Roll {
     ///
     /// # Ok::<(), DiceError>(())
     /// ```
+    ///
+    /// Fate/Fudge dice are written as `dF`.
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use dnd_dice_roller::roll::Roll;
+    /// # use dnd_dice_roller::error::DiceError;
+    ///
+    /// let roll = Roll::from_str("4dF+2")?;
+    ///
+    /// # Ok::<(), DiceError>(())
+    /// ```
+    ///
+    /// Roll and keep dice are written as `XkY`, rolling X exploding d10s and keeping the Y highest, with `e` for emphasis to reroll 1s.
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use dnd_dice_roller::roll::Roll;
+    /// # use dnd_dice_roller::error::DiceError;
+    ///
+    /// let roll = Roll::from_str("7k3e + 5")?;
+    ///
+    /// # Ok::<(), DiceError>(())
+    /// ```
+    ///
+    /// Custom dice list their faces in braces, which may be numbers or symbols.
+    /// ```
+    /// use std::str::FromStr;
+    ///
+    /// use dnd_dice_roller::roll::Roll;
+    /// # use dnd_dice_roller::error::DiceError;
+    ///
+    /// let roll = Roll::from_str("2d{2,4,6,8} + d{skull,blank,blank}")?;
+    ///
+    /// # Ok::<(), DiceError>(())
+    /// ```
     /// # Errors
     /// Errors can occur if the dice input string is in the wrong format `DiceError::ParseError`.
     fn from_str(input: &str) -> Result<Self, Self::Err> {
-        let roll = parse_line(&input)?
-            .iter()
-            .map(|dice| {
-                DiceSet::new(
-                    dice.iter()
-                        .map(|d| Dice::from_parsed_dice_roll(d))
-                        .collect(),
-                )
-            })
-            .collect();
-        Ok(Self::new(roll))
+        Ok(Self::new(parse_dice_sets(input, &NamedDice::new())?))
     }
 }
 
@@ -137,6 +227,7 @@ impl FromStr for Roll {
 mod test {
     use super::*;
 
+    use crate::dice::DiceKind;
     use crate::dice_result::RollResult;
     use rand::SeedableRng;
 
@@ -236,6 +327,25 @@ mod test {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn produces_predictable_results_fudge_dice() {
+        let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let dice = Roll::from_str("4dF+2").expect("No error parsing dice");
+        let result = dice.roll_from_rng(rng);
+        let roll = &result[0].dice_results[0];
+        assert_eq!(roll.kind, DiceKind::Fudge);
+        assert_eq!(roll.first_roll, vec![2, 3, 1, 3]);
+        assert_eq!(roll.result, 3);
+        assert_eq!(result[0].final_result, 3);
+        assert_eq!(roll.to_string(), "[ , +, -, +]");
+    }
+
+    #[test]
+    fn invalid_fudge_dice_fail_to_parse() {
+        assert!(Roll::from_str("4dG").is_err());
+        assert!(Roll::from_str("dF+").is_err());
+    }
+
     #[test]
     fn produces_predictable_results_separate_dice() {
         let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
@@ -248,4 +358,25 @@ mod test {
         ];
         assert_eq!(result, expected);
     }
+
+    #[test]
+    fn formatted_notation_parses_to_the_same_roll() {
+        for input in [
+            "d6",
+            "3d6 + 1, d20 a",
+            "2d20 - 2 d + d4 - d6",
+            "-d4",
+            "4dF + 2",
+            "2d{2,3,3,4,4,5}",
+            "d{skull,blank,blank}",
+            "7k3e + 5",
+        ] {
+            let roll = Roll::from_str(input).expect("Valid roll");
+            let formatted = roll.to_string();
+            let reparsed = Roll::from_str(&formatted).expect("Formatted roll is valid");
+            assert_eq!(roll.dice_sets(), reparsed.dice_sets(), "{formatted}");
+        }
+        let roll = Roll::from_str("3d6+1,d20a").expect("Valid roll");
+        assert_eq!(roll.to_string(), "3d6 + 1, d20 a");
+    }
 }
@@ -0,0 +1,237 @@
+use crate::{
+    dice::{Dice, Operation, RollType},
+    dice_result::RollResult,
+};
+
+use rand::Rng;
+use std::cmp::max;
+
+/// The target number for most trait rolls.
+pub const STANDARD_TARGET_NUMBER: i32 = 4;
+/// How far a total must beat the target number by for each raise.
+const RAISE: i32 = 4;
+/// The sides of the Wild Die rolled alongside a Wild Card's trait die.
+const WILD_DIE_SIDES: u32 = 6;
+
+/// Represents a Savage Worlds trait roll, an exploding trait die with an exploding d6 Wild Die for Wild Cards.
+#[derive(Clone, PartialEq, Debug)]
+pub struct TraitRoll {
+    trait_die: Dice,
+    wild_die: Option<Dice>,
+}
+
+impl TraitRoll {
+    /// Creates a new `TraitRoll`. The modifier applies to both the trait die and the Wild Die.
+    /// Extras, who are not Wild Cards, roll only the trait die.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::savage_worlds::TraitRoll;
+    ///
+    /// // A Wild Card with a d8 in Fighting and a +1 bonus
+    /// let trait_roll = TraitRoll::new(8, Some(1), true);
+    /// ```
+    #[must_use]
+    pub fn new(trait_die_sides: u32, modifier: Option<i32>, wild_card: bool) -> Self {
+        let exploding = |sides| {
+            Dice::new(1, sides, modifier, RollType::Regular, Operation::Addition).exploding()
+        };
+        TraitRoll {
+            trait_die: exploding(trait_die_sides),
+            wild_die: if wild_card {
+                Some(exploding(WILD_DIE_SIDES))
+            } else {
+                None
+            },
+        }
+    }
+
+    /// Rolls the trait die and any Wild Die. Using underlying OS RNG for the dice roll.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::savage_worlds::TraitRoll;
+    ///
+    /// let result = TraitRoll::new(8, None, true).roll_trait();
+    /// assert!(result.wild_roll.is_some());
+    /// ```
+    #[must_use]
+    pub fn roll_trait(&self) -> TraitRollResult {
+        let mut rng = rand::thread_rng();
+        self.roll_trait_from_rng(&mut rng)
+    }
+
+    /// Rolls the trait die and any Wild Die, keeping the higher. Uses a source of RNG passed in. Useful for testing.
+    ///
+    /// # Examples
+    /// ```
+    /// use rand::SeedableRng;
+    /// use dnd_dice_roller::savage_worlds::TraitRoll;
+    ///
+    /// let rng = rand_pcg::Pcg64Mcg::seed_from_u64(42);
+    /// let result = TraitRoll::new(6, None, false).roll_trait_from_rng(rng);
+    /// assert_eq!(result.result, 2);
+    /// ```
+    pub fn roll_trait_from_rng<R: Rng + Sized>(&self, mut rng: R) -> TraitRollResult {
+        let trait_roll = self.trait_die.roll_dice_from_rng(&mut rng);
+        let wild_roll = self
+            .wild_die
+            .as_ref()
+            .map(|wild_die| wild_die.roll_dice_from_rng(&mut rng));
+        let result = wild_roll.as_ref().map_or(trait_roll.result, |wild_roll| {
+            max(trait_roll.result, wild_roll.result)
+        });
+
+        TraitRollResult {
+            trait_roll,
+            wild_roll,
+            result,
+        }
+    }
+}
+
+/// Represents the result of a `TraitRoll`.
+#[derive(PartialEq, Debug)]
+pub struct TraitRollResult {
+    /// The roll of the trait die, including any aces and the modifier.
+    pub trait_roll: RollResult,
+    /// The roll of the Wild Die, including any aces and the modifier. Only present for Wild Cards.
+    pub wild_roll: Option<RollResult>,
+    /// The higher of the trait die and Wild Die.
+    pub result: i32,
+}
+
+impl TraitRollResult {
+    /// Whether both the trait die and the Wild Die rolled a natural 1. Extras never critically fail.
+    ///
+    /// # Examples
+    /// ```
+    /// use rand::SeedableRng;
+    /// use dnd_dice_roller::savage_worlds::TraitRoll;
+    ///
+    /// let rng = rand_pcg::Pcg64Mcg::seed_from_u64(42);
+    /// let result = TraitRoll::new(6, None, true).roll_trait_from_rng(rng);
+    /// assert!(!result.is_critical_failure());
+    /// ```
+    #[must_use]
+    pub fn is_critical_failure(&self) -> bool {
+        let natural_one = |roll: &RollResult| roll.first_roll == [1];
+        natural_one(&self.trait_roll) && self.wild_roll.as_ref().is_some_and(natural_one)
+    }
+
+    /// Whether the result meets the target number, which is usually `STANDARD_TARGET_NUMBER`. A critical failure never succeeds.
+    #[must_use]
+    pub fn succeeds(&self, target_number: i32) -> bool {
+        !self.is_critical_failure() && self.result >= target_number
+    }
+
+    /// The number of raises, one for every 4 the result beats the target number by.
+    ///
+    /// # Examples
+    /// ```
+    /// use rand::SeedableRng;
+    /// use dnd_dice_roller::savage_worlds::{TraitRoll, STANDARD_TARGET_NUMBER};
+    ///
+    /// let rng = rand_pcg::Pcg64Mcg::seed_from_u64(42);
+    /// let result = TraitRoll::new(6, Some(10), false).roll_trait_from_rng(rng);
+    /// assert_eq!(result.result, 12);
+    /// assert_eq!(result.raises(STANDARD_TARGET_NUMBER), 2);
+    /// ```
+    #[must_use]
+    #[allow(clippy::cast_sign_loss)]
+    pub fn raises(&self, target_number: i32) -> u32 {
+        if self.succeeds(target_number) {
+            ((self.result - target_number) / RAISE) as u32
+        } else {
+            0
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::scripted_rng::ScriptedRng;
+    use rand::SeedableRng;
+
+    const SEED: u64 = 42;
+
+    fn result(trait_die: u32, wild_die: Option<u32>) -> TraitRollResult {
+        #[allow(clippy::cast_possible_wrap)]
+        let roll = |value: u32| RollResult::new(vec![value], None, value as i32);
+        let trait_roll = roll(trait_die);
+        let wild_roll = wild_die.map(roll);
+        let result = max(
+            trait_roll.result,
+            wild_roll.as_ref().map_or(0, |wild_roll| wild_roll.result),
+        );
+        TraitRollResult {
+            trait_roll,
+            wild_roll,
+            result,
+        }
+    }
+
+    #[test]
+    fn produces_predictable_results() {
+        let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let result = TraitRoll::new(4, None, true).roll_trait_from_rng(rng);
+        assert_eq!(result.trait_roll.first_roll, vec![2]);
+        assert_eq!(result.trait_roll.result, 2);
+        // The Wild Die aces on a 6 and adds the 5 rolled after it
+        assert_eq!(result.wild_roll, Some(RollResult::new(vec![11], None, 11)));
+        assert_eq!(result.result, 11);
+    }
+
+    #[test]
+    fn scripted_aces_count_raises() {
+        let rng = ScriptedRng::new(8, &[8, 8, 3]).then(6, &[2]);
+        let result = TraitRoll::new(8, Some(1), true).roll_trait_from_rng(rng);
+        assert_eq!(result.trait_roll.first_roll, vec![19]);
+        assert_eq!(result.trait_roll.result, 20);
+        assert_eq!(result.wild_roll.as_ref().map(|roll| roll.result), Some(3));
+        assert_eq!(result.result, 20);
+        assert_eq!(result.raises(STANDARD_TARGET_NUMBER), 4);
+    }
+
+    #[test]
+    fn extras_do_not_roll_a_wild_die() {
+        let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let result = TraitRoll::new(8, None, false).roll_trait_from_rng(rng);
+        assert!(result.wild_roll.is_none());
+    }
+
+    #[test]
+    fn keeps_the_higher_die() {
+        assert_eq!(result(3, Some(9)).result, 9);
+        assert_eq!(result(10, Some(2)).result, 10);
+    }
+
+    #[test]
+    fn double_ones_critically_fail() {
+        assert!(result(1, Some(1)).is_critical_failure());
+        assert!(!result(1, Some(2)).is_critical_failure());
+        assert!(!result(1, None).is_critical_failure());
+        assert!(!result(1, Some(1)).succeeds(-4));
+    }
+
+    #[test]
+    fn counts_raises() {
+        assert_eq!(result(3, Some(2)).raises(STANDARD_TARGET_NUMBER), 0);
+        assert_eq!(result(7, Some(2)).raises(STANDARD_TARGET_NUMBER), 0);
+        assert_eq!(result(8, Some(2)).raises(STANDARD_TARGET_NUMBER), 1);
+        assert_eq!(result(2, Some(14)).raises(STANDARD_TARGET_NUMBER), 2);
+        assert_eq!(result(14, None).raises(6), 2);
+    }
+
+    #[test]
+    fn aces_explode() {
+        let mut rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let trait_roll = TraitRoll::new(4, None, true);
+        let aced = (0..200)
+            .map(|_| trait_roll.roll_trait_from_rng(&mut rng))
+            .any(|result| result.trait_roll.result > 4);
+        assert!(aced);
+    }
+}
@@ -0,0 +1,193 @@
+use alloc::collections::VecDeque;
+use rand::RngCore;
+
+/// Represents a source of RNG which rolls a predetermined sequence of faces, for tests and tutorials.
+///
+/// Each face is scripted with the sides of the dice it is for, so that `gen_range(1..=sides)` returns exactly that face.
+/// Every call to `next_u32` or `next_u64` uses up one scripted face.
+///
+/// # Examples
+/// ```
+/// use dnd_dice_roller::dice::{Dice, Operation, RollType};
+/// use dnd_dice_roller::scripted_rng::ScriptedRng;
+///
+/// let dice = Dice::new(1, 20, Some(2), RollType::Advantage, Operation::Addition);
+/// let result = dice.roll_dice_from_rng(ScriptedRng::new(20, &[20, 1]));
+/// assert_eq!(result.first_roll, vec![20]);
+/// assert_eq!(result.second_roll, Some(vec![1]));
+/// assert_eq!(result.result, 22);
+/// ```
+#[derive(Clone, PartialEq, Debug)]
+pub struct ScriptedRng {
+    values: VecDeque<u32>,
+}
+
+impl ScriptedRng {
+    /// Creates a new `ScriptedRng` which rolls the faces given on dice with the number of sides given.
+    ///
+    /// # Panics
+    /// Panics if a face is not between 1 and the number of sides.
+    #[must_use]
+    pub fn new(sides: u32, faces: &[u32]) -> Self {
+        ScriptedRng {
+            values: VecDeque::new(),
+        }
+        .then(sides, faces)
+    }
+
+    /// Scripts more faces to be rolled after those already scripted, on dice with the number of sides given.
+    ///
+    /// # Examples
+    /// ```
+    /// use std::str::FromStr;
+    /// use dnd_dice_roller::roll::Roll;
+    /// use dnd_dice_roller::scripted_rng::ScriptedRng;
+    /// # use dnd_dice_roller::error::DiceError;
+    ///
+    /// let roll = Roll::from_str("d20 + 5, 2d6 + 3")?;
+    /// let rng = ScriptedRng::new(20, &[20]).then(6, &[4, 5]);
+    /// let results = roll.roll_from_rng(rng);
+    /// assert_eq!(results[0].final_result, 25);
+    /// assert_eq!(results[1].final_result, 12);
+    /// # Ok::<(), DiceError>(())
+    /// ```
+    /// # Panics
+    /// Panics if a face is not between 1 and the number of sides.
+    #[must_use]
+    pub fn then(mut self, sides: u32, faces: &[u32]) -> Self {
+        for face in faces {
+            assert!(
+                (1..=sides).contains(face),
+                "Cannot script a {} on a dice with {} sides",
+                face,
+                sides
+            );
+            self.values.push_back(Self::value_for(*face, sides));
+        }
+        self
+    }
+
+    /// The number of scripted faces which have not been rolled yet.
+    #[must_use]
+    pub fn remaining(&self) -> usize {
+        self.values.len()
+    }
+
+    // The smallest value which `gen_range(1..=sides)` maps to the face. Being the smallest keeps it inside the zone `rand` accepts without resampling.
+    #[allow(clippy::cast_possible_truncation)]
+    fn value_for(face: u32, sides: u32) -> u32 {
+        ((u64::from(face) - 1) << 32).div_ceil(u64::from(sides)) as u32
+    }
+}
+
+impl RngCore for ScriptedRng {
+    /// # Panics
+    /// Panics if every scripted face has already been rolled.
+    fn next_u32(&mut self) -> u32 {
+        match self.values.pop_front() {
+            Some(value) => value,
+            None => panic!("ScriptedRng has no scripted faces left to roll"),
+        }
+    }
+
+    // Shifting into the high bits keeps the face when ranges are sampled from 64 bits e.g. `usize` ranges.
+    fn next_u64(&mut self) -> u64 {
+        u64::from(self.next_u32()) << 32
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        for chunk in dest.chunks_mut(4) {
+            let bytes = self.next_u32().to_le_bytes();
+            chunk.copy_from_slice(&bytes[..chunk.len()]);
+        }
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        if self.values.len() < dest.len().div_ceil(4) {
+            #[cfg(feature = "std")]
+            let error =
+                rand::Error::new("ScriptedRng has too few scripted faces left to fill the bytes");
+            #[cfg(not(feature = "std"))]
+            let error = rand::Error::from(
+                core::num::NonZeroU32::new(rand::Error::CUSTOM_START)
+                    .expect("Error code is not zero"),
+            );
+            return Err(error);
+        }
+        self.fill_bytes(dest);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::dice::{Dice, Operation, RollType};
+    use crate::year_zero::YearZeroPool;
+    use rand::Rng;
+
+    #[test]
+    fn maps_every_face_through_gen_range() {
+        for sides in [1, 2, 3, 4, 6, 7, 8, 10, 12, 20, 100, 1000] {
+            let faces: Vec<u32> = (1..=sides).collect();
+            let mut rng = ScriptedRng::new(sides, &faces);
+            for face in faces {
+                assert_eq!(rng.gen_range(1..=sides), face);
+            }
+            assert_eq!(rng.remaining(), 0);
+        }
+    }
+
+    #[test]
+    fn maps_faces_through_usize_ranges() {
+        let mut rng = ScriptedRng::new(6, &[1, 6, 3]);
+        assert_eq!(rng.gen_range(0..6_usize), 0);
+        assert_eq!(rng.gen_range(0..6_usize), 5);
+        assert_eq!(rng.gen_range(0..6_usize), 2);
+    }
+
+    #[test]
+    fn rolls_exactly_the_scripted_faces() {
+        let dice = Dice::new(3, 20, Some(1), RollType::Regular, Operation::Addition);
+        let result = dice.roll_dice_from_rng(ScriptedRng::new(20, &[20, 1, 13]));
+        assert_eq!(result.first_roll, vec![20, 1, 13]);
+        assert_eq!(result.result, 35);
+    }
+
+    #[test]
+    fn scripts_exploding_dice() {
+        let dice = Dice::new(2, 6, None, RollType::Regular, Operation::Addition).exploding();
+        let result = dice.roll_dice_from_rng(ScriptedRng::new(6, &[6, 6, 2, 4]));
+        assert_eq!(result.first_roll, vec![14, 4]);
+    }
+
+    #[test]
+    fn scripts_other_rolls() {
+        let rng = ScriptedRng::new(6, &[6, 1, 3]);
+        let result = YearZeroPool::new(1, 1, 1).roll_pool_from_rng(rng);
+        assert_eq!(result.successes(), 1);
+        assert_eq!(result.banes(), 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "no scripted faces left")]
+    fn panics_when_exhausted() {
+        let dice = Dice::new(2, 20, None, RollType::Regular, Operation::Addition);
+        let _ = dice.roll_dice_from_rng(ScriptedRng::new(20, &[20]));
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot script a 7 on a dice with 6 sides")]
+    fn rejects_impossible_faces() {
+        let _ = ScriptedRng::new(6, &[7]);
+    }
+
+    #[test]
+    fn try_fill_bytes_errors_when_exhausted() {
+        let mut rng = ScriptedRng::new(6, &[1]);
+        let mut bytes = [0; 8];
+        assert!(rng.try_fill_bytes(&mut bytes).is_err());
+        assert_eq!(rng.remaining(), 1);
+    }
+}
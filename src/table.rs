@@ -0,0 +1,567 @@
+use crate::{dice::Dice, dice_result::RollResult, error::DiceError};
+
+use rand::Rng;
+
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+
+/// How many tables deep a roll may go before it is assumed a table references itself.
+const MAX_DEPTH: usize = 32;
+
+/// Represents what an entry on a table produces when rolled.
+#[derive(Clone, PartialEq, Debug)]
+pub enum TableResult {
+    /// A result to be shown as is. e.g. `50 gold pieces`
+    Text(String),
+    /// A reference to another table, by name, which should be rolled on.
+    Table(String),
+    /// Roll on this table again the given number of times. e.g. `Roll twice`
+    /// Further `RollAgain` results rolled as a part of this are rerolled.
+    RollAgain(u32),
+}
+
+/// Represents a single row on a table. e.g. `01-15: 50 gold pieces`
+#[derive(Clone, PartialEq, Debug)]
+pub struct TableEntry {
+    /// The dice results which select this entry.
+    pub range: RangeInclusive<i32>,
+    /// What the entry produces.
+    pub result: TableResult,
+}
+
+impl TableEntry {
+    /// Creates a new `TableEntry` covering the results `start` to `end` inclusive.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::table::{TableEntry, TableResult};
+    ///
+    /// let entry = TableEntry::new(1, 15, TableResult::Text("50 gold pieces".to_string()));
+    /// ```
+    #[must_use]
+    pub fn new(start: i32, end: i32, result: TableResult) -> Self {
+        TableEntry {
+            range: start..=end,
+            result,
+        }
+    }
+}
+
+/// Represents a random table, where the result of rolling some dice selects an entry. e.g. A d100 loot table.
+#[derive(Debug)]
+pub struct Table {
+    name: String,
+    dice: Dice,
+    entries: Vec<TableEntry>,
+}
+
+/// Represents the result of rolling on a `Table`.
+#[derive(PartialEq, Debug)]
+pub struct TableRollResult {
+    /// The name of the table rolled on.
+    pub table: String,
+    /// The roll which selected the entry.
+    pub roll: RollResult,
+    /// What the selected entry produces.
+    pub result: TableResult,
+    /// The rolls made because of the selected entry. Rolls on a referenced table, or on this table for `TableResult::RollAgain`.
+    pub nested: Vec<TableRollResult>,
+}
+
+impl TableRollResult {
+    /// All of the text results produced by this roll and any nested rolls, in the order they were rolled.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::dice::{Dice, RollType, Operation};
+    /// use dnd_dice_roller::table::{Table, TableEntry, TableResult};
+    /// # use dnd_dice_roller::error::DiceError;
+    ///
+    /// let dice = Dice::new(1, 2, None, RollType::Regular, Operation::Addition);
+    /// let entries = vec![
+    ///     TableEntry::new(1, 1, TableResult::Text("Heads".to_string())),
+    ///     TableEntry::new(2, 2, TableResult::Text("Tails".to_string())),
+    /// ];
+    /// let table = Table::new("Coin", dice, entries)?;
+    /// let result = table.roll()?;
+    /// assert_eq!(result.texts().len(), 1);
+    /// # Ok::<(), DiceError>(())
+    /// ```
+    #[must_use]
+    pub fn texts(&self) -> Vec<&str> {
+        let mut texts = Vec::new();
+        if let TableResult::Text(text) = &self.result {
+            texts.push(text.as_str());
+        }
+        for nested in &self.nested {
+            texts.extend(nested.texts());
+        }
+        texts
+    }
+}
+
+impl Table {
+    /// Creates a new `Table`.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::dice::{Dice, RollType, Operation};
+    /// use dnd_dice_roller::table::{Table, TableEntry, TableResult};
+    /// # use dnd_dice_roller::error::DiceError;
+    ///
+    /// let dice = Dice::new(1, 100, None, RollType::Regular, Operation::Addition);
+    /// let entries = vec![
+    ///     TableEntry::new(1, 15, TableResult::Text("50 gold pieces".to_string())),
+    ///     TableEntry::new(16, 99, TableResult::Table("Gems".to_string())),
+    ///     TableEntry::new(100, 100, TableResult::RollAgain(2)),
+    /// ];
+    /// let table = Table::new("Loot", dice, entries)?;
+    /// # Ok::<(), DiceError>(())
+    /// ```
+    ///
+    /// # Errors
+    /// Errors when every result the dice can produce is not covered by exactly one entry (`DiceError::TableGap`, `DiceError::TableOverlap`),
+    /// when an entry is outside the results the dice can produce or selects none of them (`DiceError::TableOutOfRange`) or when no entry produces a result (`DiceError::TableWithoutResults`).
+    pub fn new(name: &str, dice: Dice, mut entries: Vec<TableEntry>) -> Result<Self, DiceError> {
+        entries.sort_by_key(|entry| *entry.range.start());
+        let table = Table {
+            name: name.to_string(),
+            dice,
+            entries,
+        };
+        table.validate()?;
+        Ok(table)
+    }
+
+    /// The name of the table.
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The dice rolled to select an entry.
+    #[must_use]
+    pub fn dice(&self) -> &Dice {
+        &self.dice
+    }
+
+    /// The entries on the table, ordered by the results which select them.
+    #[must_use]
+    pub fn entries(&self) -> &[TableEntry] {
+        &self.entries
+    }
+
+    /// Rolls on the table and produces a `TableRollResult`. Using underlying OS RNG for the dice roll.
+    ///
+    /// # Errors
+    /// Errors with `DiceError::UnknownTable` if the selected entry references another table, use `Tables` for tables which reference each other,
+    /// or `DiceError::TableRollUncovered` if exploding dice roll past every entry.
+    pub fn roll(&self) -> Result<TableRollResult, DiceError> {
+        let mut rng = rand::thread_rng();
+        self.roll_from_rng(&mut rng)
+    }
+
+    /// Rolls on the table and produces a `TableRollResult`. Uses a source of RNG passed in. Useful for testing.
+    ///
+    /// # Examples
+    /// ```
+    /// use rand::SeedableRng;
+    /// use dnd_dice_roller::dice::{Dice, RollType, Operation};
+    /// use dnd_dice_roller::table::{Table, TableEntry, TableResult};
+    /// # use dnd_dice_roller::error::DiceError;
+    ///
+    /// let rng = rand_pcg::Pcg64Mcg::seed_from_u64(42);
+    /// let dice = Dice::new(1, 6, None, RollType::Regular, Operation::Addition);
+    /// let entries = vec![
+    ///     TableEntry::new(1, 3, TableResult::Text("Orcs".to_string())),
+    ///     TableEntry::new(4, 6, TableResult::Text("Goblins".to_string())),
+    /// ];
+    /// let table = Table::new("Encounters", dice, entries)?;
+    /// let result = table.roll_from_rng(rng)?;
+    /// assert_eq!(result.texts(), vec!["Orcs"]);
+    /// # Ok::<(), DiceError>(())
+    /// ```
+    ///
+    /// # Errors
+    /// Errors with `DiceError::UnknownTable` if the selected entry references another table, use `Tables` for tables which reference each other,
+    /// or `DiceError::TableRollUncovered` if exploding dice roll past every entry.
+    pub fn roll_from_rng<R: Rng + Sized>(&self, mut rng: R) -> Result<TableRollResult, DiceError> {
+        self.roll_with_tables(None, &mut rng, 0, false)
+    }
+
+    fn roll_with_tables<R: Rng + Sized>(
+        &self,
+        tables: Option<&Tables>,
+        rng: &mut R,
+        depth: usize,
+        rerolling: bool,
+    ) -> Result<TableRollResult, DiceError> {
+        if depth > MAX_DEPTH {
+            return Err(DiceError::TableRecursion(self.name.clone()));
+        }
+
+        let (roll, entry) = loop {
+            let roll = self.dice.roll_dice_from_rng(&mut *rng);
+            let entry = self.entry_for(roll.result)?;
+            // Rolls made because of a `RollAgain` ignore further `RollAgain` entries
+            if !(rerolling && matches!(entry.result, TableResult::RollAgain(_))) {
+                break (roll, entry);
+            }
+        };
+
+        let nested = match &entry.result {
+            TableResult::Text(_) => Vec::new(),
+            TableResult::Table(name) => {
+                let table = tables
+                    .and_then(|tables| tables.get(name))
+                    .ok_or_else(|| DiceError::UnknownTable(name.clone()))?;
+                vec![table.roll_with_tables(tables, rng, depth + 1, false)?]
+            }
+            TableResult::RollAgain(times) => (0..*times)
+                .map(|_| self.roll_with_tables(tables, rng, depth + 1, true))
+                .collect::<Result<_, _>>()?,
+        };
+
+        Ok(TableRollResult {
+            table: self.name.clone(),
+            roll,
+            result: entry.result.clone(),
+            nested,
+        })
+    }
+
+    fn entry_for(&self, value: i32) -> Result<&TableEntry, DiceError> {
+        // Exploding dice can roll past the results their distribution is cut off at, so these may have no entry
+        self.entries
+            .iter()
+            .find(|entry| entry.range.contains(&value))
+            .ok_or_else(|| DiceError::TableRollUncovered {
+                table: self.name.clone(),
+                value,
+            })
+    }
+
+    fn validate(&self) -> Result<(), DiceError> {
+        // Only the results the dice can roll need an entry, so custom dice such as `d{2,4,6,8}` may leave gaps
+        let rollable: Vec<i32> = self
+            .dice
+            .distribution()
+            .iter()
+            .filter(|(_, probability)| *probability > 0.0)
+            .map(|(value, _)| value)
+            .collect();
+        let (Some(&min), Some(&max)) = (rollable.first(), rollable.last()) else {
+            return Err(DiceError::TableWithoutResults(self.name.clone()));
+        };
+
+        let mut previous_end: Option<i32> = None;
+        for entry in &self.entries {
+            let (start, end) = (*entry.range.start(), *entry.range.end());
+            if start > end
+                || start < min
+                || end > max
+                || !rollable.iter().any(|value| entry.range.contains(value))
+            {
+                return Err(DiceError::TableOutOfRange {
+                    table: self.name.clone(),
+                    start,
+                    end,
+                });
+            }
+            if previous_end.is_some_and(|previous_end| start <= previous_end) {
+                return Err(DiceError::TableOverlap {
+                    table: self.name.clone(),
+                    value: start,
+                });
+            }
+            previous_end = Some(end);
+        }
+
+        let is_covered = |value: &i32| self.entries.iter().any(|entry| entry.range.contains(value));
+        if let Some(index) = rollable.iter().position(|value| !is_covered(value)) {
+            // Report every rollable result up to the next covered one as a single gap
+            let gap: Vec<i32> = rollable[index..]
+                .iter()
+                .copied()
+                .take_while(|value| !is_covered(value))
+                .collect();
+            return Err(DiceError::TableGap {
+                table: self.name.clone(),
+                start: gap[0],
+                end: gap[gap.len() - 1],
+            });
+        }
+
+        if self
+            .entries
+            .iter()
+            .all(|entry| matches!(entry.result, TableResult::RollAgain(_)))
+        {
+            return Err(DiceError::TableWithoutResults(self.name.clone()));
+        }
+        Ok(())
+    }
+}
+
+/// Represents a collection of named tables which may reference each other.
+#[derive(Debug, Default)]
+pub struct Tables {
+    tables: HashMap<String, Table>,
+}
+
+impl Tables {
+    /// Creates an empty collection of tables.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a table, replacing any existing table with the same name.
+    pub fn insert(&mut self, table: Table) {
+        self.tables.insert(table.name.clone(), table);
+    }
+
+    /// Gets a table by name.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&Table> {
+        self.tables.get(name)
+    }
+
+    /// Rolls on the named table, following references to other tables, and produces a `TableRollResult`. Using underlying OS RNG for the dice roll.
+    ///
+    /// # Errors
+    /// Errors with `DiceError::UnknownTable` if a table that does not exist is rolled on, `DiceError::TableRecursion` if tables reference each other endlessly,
+    /// or `DiceError::TableRollUncovered` if exploding dice roll past every entry.
+    pub fn roll(&self, name: &str) -> Result<TableRollResult, DiceError> {
+        let mut rng = rand::thread_rng();
+        self.roll_from_rng(name, &mut rng)
+    }
+
+    /// Rolls on the named table, following references to other tables, and produces a `TableRollResult`. Uses a source of RNG passed in. Useful for testing.
+    ///
+    /// # Examples
+    /// ```
+    /// use rand::SeedableRng;
+    /// use dnd_dice_roller::dice::{Dice, RollType, Operation};
+    /// use dnd_dice_roller::table::{Table, TableEntry, TableResult, Tables};
+    /// # use dnd_dice_roller::error::DiceError;
+    ///
+    /// let rng = rand_pcg::Pcg64Mcg::seed_from_u64(42);
+    /// let mut tables = Tables::new();
+    /// tables.insert(Table::new(
+    ///     "Loot",
+    ///     Dice::new(1, 2, None, RollType::Regular, Operation::Addition),
+    ///     vec![
+    ///         TableEntry::new(1, 1, TableResult::Table("Gems".to_string())),
+    ///         TableEntry::new(2, 2, TableResult::Text("Nothing".to_string())),
+    ///     ],
+    /// )?);
+    /// tables.insert(Table::new(
+    ///     "Gems",
+    ///     Dice::new(1, 1, None, RollType::Regular, Operation::Addition),
+    ///     vec![TableEntry::new(1, 1, TableResult::Text("Ruby".to_string()))],
+    /// )?);
+    /// let result = tables.roll_from_rng("Loot", rng)?;
+    /// assert_eq!(result.texts(), vec!["Ruby"]);
+    /// # Ok::<(), DiceError>(())
+    /// ```
+    ///
+    /// # Errors
+    /// Errors with `DiceError::UnknownTable` if a table that does not exist is rolled on, `DiceError::TableRecursion` if tables reference each other endlessly,
+    /// or `DiceError::TableRollUncovered` if exploding dice roll past every entry.
+    pub fn roll_from_rng<R: Rng + Sized>(
+        &self,
+        name: &str,
+        mut rng: R,
+    ) -> Result<TableRollResult, DiceError> {
+        let table = self
+            .get(name)
+            .ok_or_else(|| DiceError::UnknownTable(name.to_string()))?;
+        table.roll_with_tables(Some(self), &mut rng, 0, false)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::dice::{Face, Operation, RollType};
+    use crate::scripted_rng::ScriptedRng;
+    use rand::SeedableRng;
+
+    const SEED: u64 = 42;
+
+    fn d(sides: u32) -> Dice {
+        Dice::new(1, sides, None, RollType::Regular, Operation::Addition)
+    }
+
+    fn text(text: &str) -> TableResult {
+        TableResult::Text(text.to_string())
+    }
+
+    #[test]
+    fn rejects_gaps() {
+        let entries = vec![
+            TableEntry::new(1, 2, text("A")),
+            TableEntry::new(5, 6, text("B")),
+        ];
+        match Table::new("Gappy", d(6), entries) {
+            Err(DiceError::TableGap { start, end, .. }) => assert_eq!((start, end), (3, 4)),
+            other => panic!("Expected a gap, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn rejects_missing_final_results() {
+        let entries = vec![TableEntry::new(1, 4, text("A"))];
+        match Table::new("Short", d(6), entries) {
+            Err(DiceError::TableGap { start, end, .. }) => assert_eq!((start, end), (5, 6)),
+            other => panic!("Expected a gap, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn rejects_overlaps() {
+        let entries = vec![
+            TableEntry::new(1, 4, text("A")),
+            TableEntry::new(3, 6, text("B")),
+        ];
+        match Table::new("Overlapping", d(6), entries) {
+            Err(DiceError::TableOverlap { value, .. }) => assert_eq!(value, 3),
+            other => panic!("Expected an overlap, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn rejects_unrollable_entries() {
+        let entries = vec![
+            TableEntry::new(1, 6, text("A")),
+            TableEntry::new(7, 8, text("B")),
+        ];
+        assert!(matches!(
+            Table::new("Too long", d(6), entries),
+            Err(DiceError::TableOutOfRange {
+                start: 7,
+                end: 8,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn custom_dice_only_need_entries_for_their_faces() {
+        let faces = [2, 4, 6, 8]
+            .iter()
+            .map(|value| Face::Number(*value))
+            .collect();
+        let dice = Dice::custom(1, faces, None, RollType::Regular, Operation::Addition)
+            .expect("Faces given");
+        let entries = vec![
+            TableEntry::new(2, 2, text("A")),
+            TableEntry::new(3, 4, text("B")),
+            TableEntry::new(6, 8, text("C")),
+        ];
+        assert!(Table::new("Evens", dice.clone(), entries).is_ok());
+
+        let entries = vec![
+            TableEntry::new(2, 4, text("A")),
+            TableEntry::new(5, 5, text("B")),
+            TableEntry::new(6, 8, text("C")),
+        ];
+        assert!(matches!(
+            Table::new("Odd entry", dice.clone(), entries),
+            Err(DiceError::TableOutOfRange {
+                start: 5,
+                end: 5,
+                ..
+            })
+        ));
+
+        let entries = vec![TableEntry::new(2, 4, text("A"))];
+        match Table::new("Missing", dice, entries) {
+            Err(DiceError::TableGap { start, end, .. }) => assert_eq!((start, end), (6, 8)),
+            other => panic!("Expected a gap, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn rejects_tables_which_only_roll_again() {
+        let entries = vec![TableEntry::new(1, 6, TableResult::RollAgain(2))];
+        assert!(matches!(
+            Table::new("Endless", d(6), entries),
+            Err(DiceError::TableWithoutResults(_))
+        ));
+    }
+
+    #[test]
+    fn entries_may_be_given_in_any_order() {
+        let entries = vec![
+            TableEntry::new(4, 6, text("B")),
+            TableEntry::new(1, 3, text("A")),
+        ];
+        let table = Table::new("Unordered", d(6), entries).expect("Valid table");
+        assert_eq!(table.entries()[0].result, text("A"));
+    }
+
+    #[test]
+    fn roll_again_rerolls_further_roll_again_results() {
+        let entries = vec![
+            TableEntry::new(1, 1, TableResult::RollAgain(2)),
+            TableEntry::new(2, 2, text("A")),
+        ];
+        let mut tables = Tables::new();
+        tables.insert(Table::new("Twice", d(2), entries).expect("Valid table"));
+        let mut rolled_again = false;
+        for seed in 0..10 {
+            let rng = rand_pcg::Pcg64Mcg::seed_from_u64(seed);
+            let result = tables.roll_from_rng("Twice", rng).expect("Valid roll");
+            if result.result == TableResult::RollAgain(2) {
+                rolled_again = true;
+                assert_eq!(result.texts(), vec!["A", "A"]);
+            } else {
+                assert_eq!(result.texts(), vec!["A"]);
+            }
+        }
+        assert!(rolled_again);
+    }
+
+    #[test]
+    fn unknown_table_reference_errors() {
+        let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let entries = vec![TableEntry::new(1, 1, TableResult::Table("Missing".into()))];
+        let table = Table::new("Dangling", d(1), entries).expect("Valid table");
+        assert!(matches!(
+            table.roll_from_rng(rng),
+            Err(DiceError::UnknownTable(name)) if name == "Missing"
+        ));
+    }
+
+    #[test]
+    fn exploding_dice_rolling_past_every_entry_error() {
+        let dice = d(2).exploding();
+        let max = dice.distribution().max().expect("Dice have results");
+        let entries = vec![TableEntry::new(1, max, text("A"))];
+        let mut tables = Tables::new();
+        tables.insert(Table::new("Exploding", dice, entries).expect("Valid table"));
+
+        let mut faces = vec![2; 60];
+        faces.push(1);
+        let rng = ScriptedRng::new(2, &faces);
+        assert!(matches!(
+            tables.roll_from_rng("Exploding", rng),
+            Err(DiceError::TableRollUncovered { value: 121, .. })
+        ));
+    }
+
+    #[test]
+    fn self_referencing_tables_error() {
+        let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let entries = vec![TableEntry::new(1, 1, TableResult::Table("Loop".into()))];
+        let mut tables = Tables::new();
+        tables.insert(Table::new("Loop", d(1), entries).expect("Valid table"));
+        assert!(matches!(
+            tables.roll_from_rng("Loop", rng),
+            Err(DiceError::TableRecursion(_))
+        ));
+    }
+}
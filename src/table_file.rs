@@ -0,0 +1,676 @@
+use crate::{
+    dice::{Dice, NamedDice},
+    error::DiceError,
+    notation::parse_dice_sets,
+    table::{Table, TableEntry, TableResult, Tables},
+};
+
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Represents the file formats a table can be loaded from.
+///
+/// Every format holds a dice expression (e.g. `d100`) and rows of `range,result`:
+/// - CSV: A header row whose first column is the dice expression, then one row per entry.
+/// - TOML and JSON: A `dice` key and a `rows` array of objects with `range` and `result` keys.
+///
+/// Ranges may be a single result (`7`) or two results separated by a dash (`01-15`). On percentile tables `00` stands for `100`.
+/// A result of `@Name` rolls on the table called `Name`, which must not contain path separators or `..`, while `Roll twice` or `Roll 3 times` rolls on the same table again.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum TableFormat {
+    /// Comma separated values, as exported from a spreadsheet.
+    #[cfg(feature = "csv")]
+    Csv,
+    /// A TOML document.
+    #[cfg(feature = "toml")]
+    Toml,
+    /// A JSON document.
+    #[cfg(feature = "json")]
+    Json,
+}
+
+impl TableFormat {
+    const ALL: &'static [TableFormat] = &[
+        #[cfg(feature = "csv")]
+        TableFormat::Csv,
+        #[cfg(feature = "toml")]
+        TableFormat::Toml,
+        #[cfg(feature = "json")]
+        TableFormat::Json,
+    ];
+
+    /// Determines the format of a file from its extension. e.g. `loot.csv`
+    #[must_use]
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?;
+        Self::ALL
+            .iter()
+            .copied()
+            .find(|format| format.extension().eq_ignore_ascii_case(extension))
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            #[cfg(feature = "csv")]
+            TableFormat::Csv => "csv",
+            #[cfg(feature = "toml")]
+            TableFormat::Toml => "toml",
+            #[cfg(feature = "json")]
+            TableFormat::Json => "json",
+        }
+    }
+}
+
+/// Parses a `Table` called `name` from the contents of a table file.
+///
+/// # Examples
+/// ```
+/// # #[cfg(feature = "csv")]
+/// # {
+/// use dnd_dice_roller::table_file::{parse_table, TableFormat};
+/// # use dnd_dice_roller::error::DiceError;
+///
+/// let input = "d100,Treasure\n01-60,50 gold pieces\n61-99,@Gems\n00,Roll twice\n";
+/// let table = parse_table("Treasure", input, TableFormat::Csv)?;
+/// assert_eq!(table.entries().len(), 3);
+/// # }
+/// # Ok::<(), dnd_dice_roller::error::DiceError>(())
+/// ```
+///
+/// # Errors
+/// Errors with `DiceError::TableFile`, giving the line the problem occurred on, when the input is not in the expected format or the table is invalid.
+pub fn parse_table(name: &str, input: &str, format: TableFormat) -> Result<Table, DiceError> {
+    parse_labelled_table(name, name, input, format)
+}
+
+impl Tables {
+    /// Loads a table from a file, naming it after the file e.g. `gems.toml` is called `gems`.
+    /// Any tables it references which are not yet loaded are loaded from files named after them in the same directory.
+    ///
+    /// # Errors
+    /// Errors with `DiceError::TableFileRead` if a file cannot be read or has an unsupported extension,
+    /// `DiceError::TableFile` if a file is invalid, or `DiceError::UnknownTable` if no file can be found for a referenced table.
+    pub fn load_file<P: AsRef<Path>>(&mut self, path: P) -> Result<(), DiceError> {
+        let path = path.as_ref();
+        let label = path.display().to_string();
+        let format = TableFormat::from_path(path).ok_or_else(|| DiceError::TableFileRead {
+            file: label.clone(),
+            source: io::Error::new(io::ErrorKind::InvalidInput, "Unsupported file extension"),
+        })?;
+        let name = path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let input = fs::read_to_string(path).map_err(|source| DiceError::TableFileRead {
+            file: label.clone(),
+            source,
+        })?;
+
+        let table = parse_labelled_table(&name, &label, &input, format)?;
+        let references: Vec<String> = table
+            .entries()
+            .iter()
+            .filter_map(|entry| match &entry.result {
+                TableResult::Table(reference) => Some(reference.clone()),
+                _ => None,
+            })
+            .collect();
+        // Inserted before resolving references so tables which reference each other are only loaded once
+        self.insert(table);
+
+        let directory = path.parent().unwrap_or_else(|| Path::new(""));
+        for reference in references {
+            if self.get(&reference).is_some() {
+                continue;
+            }
+            let referenced_path = TableFormat::ALL
+                .iter()
+                .map(|format| directory.join(format!("{}.{}", reference, format.extension())))
+                .find(|path| path.is_file())
+                .ok_or(DiceError::UnknownTable(reference))?;
+            self.load_file(referenced_path)?;
+        }
+        Ok(())
+    }
+}
+
+/// A row of a table file, before it has been validated.
+struct Row {
+    line: usize,
+    range: String,
+    result: String,
+}
+
+/// The contents of a table file, before it has been validated.
+struct TableDefinition {
+    dice_line: usize,
+    dice: String,
+    rows: Vec<Row>,
+}
+
+fn parse_labelled_table(
+    name: &str,
+    label: &str,
+    input: &str,
+    format: TableFormat,
+) -> Result<Table, DiceError> {
+    let definition = match format {
+        #[cfg(feature = "csv")]
+        TableFormat::Csv => read_csv(label, input)?,
+        #[cfg(feature = "toml")]
+        TableFormat::Toml => read_toml(label, input)?,
+        #[cfg(feature = "json")]
+        TableFormat::Json => read_json(label, input)?,
+    };
+    definition.into_table(name, label)
+}
+
+impl TableDefinition {
+    fn into_table(self, name: &str, label: &str) -> Result<Table, DiceError> {
+        let error = |line: usize, reason: String| DiceError::TableFile {
+            file: label.to_string(),
+            line,
+            reason,
+        };
+
+        let dice = parse_dice(&self.dice).ok_or_else(|| {
+            error(
+                self.dice_line,
+                format!(
+                    "Expected a single dice expression e.g. d100, found: {}",
+                    self.dice
+                ),
+            )
+        })?;
+        let max = dice.distribution().max().unwrap_or(0);
+
+        let mut ranges = Vec::with_capacity(self.rows.len());
+        let mut entries = Vec::with_capacity(self.rows.len());
+        for row in &self.rows {
+            let (start, end) = parse_range(&row.range, max).ok_or_else(|| {
+                error(
+                    row.line,
+                    format!("Expected a range e.g. 01-15, found: {}", row.range),
+                )
+            })?;
+            let result = parse_result(&row.result);
+            if let TableResult::Table(reference) = &result {
+                if !is_table_name(reference) {
+                    return Err(error(
+                        row.line,
+                        format!("Expected the name of a table in the same directory e.g. @Gems, found: @{reference}"),
+                    ));
+                }
+            }
+            ranges.push((row.line, start, end));
+            entries.push(TableEntry::new(start, end, result));
+        }
+
+        Table::new(name, dice, entries).map_err(|table_error| {
+            let line = match &table_error {
+                DiceError::TableOverlap { value, .. } => ranges
+                    .iter()
+                    .filter(|(_, start, end)| (start..=end).contains(&value))
+                    .nth(1)
+                    .map(|(line, _, _)| *line),
+                DiceError::TableGap { start, end, .. } => ranges
+                    .iter()
+                    .find(|(_, row_start, _)| *row_start == end + 1)
+                    .or_else(|| ranges.iter().find(|(_, _, row_end)| *row_end == start - 1))
+                    .map(|(line, _, _)| *line),
+                DiceError::TableOutOfRange { start, end, .. } => ranges
+                    .iter()
+                    .find(|(_, row_start, row_end)| row_start == start && row_end == end)
+                    .map(|(line, _, _)| *line),
+                _ => None,
+            };
+            error(line.unwrap_or(self.dice_line), table_error.to_string())
+        })
+    }
+}
+
+fn parse_dice(input: &str) -> Option<Dice> {
+    match parse_dice_sets(input, &NamedDice::new()).ok()?.as_slice() {
+        [dice_set] => match dice_set.dice() {
+            [dice] => Some(dice.clone()),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn parse_range(range: &str, max: i32) -> Option<(i32, i32)> {
+    let range = range.trim();
+    // Skip the first character so a leading minus sign is not taken as the separator
+    let separator = range
+        .char_indices()
+        .skip(1)
+        .find(|(_, c)| *c == '-' || *c == '–');
+    if let Some((index, c)) = separator {
+        Some((
+            parse_range_value(&range[..index], max)?,
+            parse_range_value(&range[index + c.len_utf8()..], max)?,
+        ))
+    } else {
+        let value = parse_range_value(range, max)?;
+        Some((value, value))
+    }
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn parse_range_value(value: &str, max: i32) -> Option<i32> {
+    let value = value.trim();
+    let parsed: i32 = value.parse().ok()?;
+    // Percentile tables write the highest result as 00, 000 etc.
+    if parsed == 0 && value.len() > 1 && 10_i32.checked_pow(value.len() as u32) == Some(max) {
+        Some(max)
+    } else {
+        Some(parsed)
+    }
+}
+
+fn parse_result(result: &str) -> TableResult {
+    let result = result.trim();
+    if let Some(name) = result.strip_prefix('@') {
+        return TableResult::Table(name.trim().to_string());
+    }
+
+    let lowercase = result.to_lowercase();
+    let times = if lowercase == "roll twice" {
+        Some(2)
+    } else {
+        lowercase
+            .strip_prefix("roll ")
+            .and_then(|rest| rest.strip_suffix(" times"))
+            .and_then(|times| times.trim().parse().ok())
+    };
+    match times {
+        Some(times) => TableResult::RollAgain(times),
+        None => TableResult::Text(result.to_string()),
+    }
+}
+
+/// Whether a reference is a plain file stem, so loading it cannot read files outside the table's directory.
+fn is_table_name(reference: &str) -> bool {
+    !reference.is_empty()
+        && !reference.contains("..")
+        && !reference.contains(|c: char| matches!(c, '/' | '\\' | ':') || c.is_control())
+}
+
+/// The line (starting at 1) of the byte `offset` into `input`.
+#[cfg(any(feature = "toml", feature = "json"))]
+fn line_of(input: &str, offset: usize) -> usize {
+    input[..offset.min(input.len())].matches('\n').count() + 1
+}
+
+#[cfg(feature = "csv")]
+#[allow(clippy::cast_possible_truncation)]
+fn read_csv(label: &str, input: &str) -> Result<TableDefinition, DiceError> {
+    let csv_error = |error: &csv::Error| DiceError::TableFile {
+        file: label.to_string(),
+        line: error
+            .position()
+            .map_or(1, |position| position.line() as usize),
+        reason: error.to_string(),
+    };
+
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .trim(csv::Trim::All)
+        .from_reader(input.as_bytes());
+    let mut records = reader.records();
+
+    let header = records
+        .next()
+        .ok_or_else(|| DiceError::TableFile {
+            file: label.to_string(),
+            line: 1,
+            reason: "Expected a header row starting with a dice expression e.g. d100".to_string(),
+        })?
+        .map_err(|error| csv_error(&error))?;
+    let dice_line = header
+        .position()
+        .map_or(1, |position| position.line() as usize);
+
+    let mut rows = Vec::new();
+    for record in records {
+        let record = record.map_err(|error| csv_error(&error))?;
+        let line = record
+            .position()
+            .map_or(0, |position| position.line() as usize);
+        match (record.get(0), record.get(1)) {
+            (Some(range), Some(result)) => rows.push(Row {
+                line,
+                range: range.to_string(),
+                result: result.to_string(),
+            }),
+            _ => {
+                return Err(DiceError::TableFile {
+                    file: label.to_string(),
+                    line,
+                    reason: "Expected a row of the form range,result".to_string(),
+                })
+            }
+        }
+    }
+
+    Ok(TableDefinition {
+        dice_line,
+        dice: header.get(0).unwrap_or_default().to_string(),
+        rows,
+    })
+}
+
+/// A range in a TOML or JSON table file, which may be written as a number or a string.
+#[cfg(any(feature = "toml", feature = "json"))]
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum FileRange {
+    Number(i32),
+    Text(String),
+}
+
+#[cfg(any(feature = "toml", feature = "json"))]
+impl FileRange {
+    fn into_string(self) -> String {
+        match self {
+            FileRange::Number(number) => number.to_string(),
+            FileRange::Text(text) => text,
+        }
+    }
+}
+
+/// A row in a TOML or JSON table file.
+#[cfg(any(feature = "toml", feature = "json"))]
+#[derive(serde::Deserialize)]
+struct FileRow {
+    range: FileRange,
+    result: String,
+}
+
+#[cfg(feature = "toml")]
+fn read_toml(label: &str, input: &str) -> Result<TableDefinition, DiceError> {
+    #[derive(serde::Deserialize)]
+    struct TomlTable {
+        dice: toml::Spanned<String>,
+        rows: Vec<toml::Spanned<FileRow>>,
+    }
+
+    let table: TomlTable = toml::from_str(input).map_err(|error| DiceError::TableFile {
+        file: label.to_string(),
+        line: error.span().map_or(1, |span| line_of(input, span.start)),
+        reason: error.message().to_string(),
+    })?;
+
+    Ok(TableDefinition {
+        dice_line: line_of(input, table.dice.span().start),
+        dice: table.dice.into_inner(),
+        rows: table
+            .rows
+            .into_iter()
+            .map(|row| {
+                let line = line_of(input, row.span().start);
+                let row = row.into_inner();
+                Row {
+                    line,
+                    range: row.range.into_string(),
+                    result: row.result,
+                }
+            })
+            .collect(),
+    })
+}
+
+#[cfg(feature = "json")]
+fn read_json(label: &str, input: &str) -> Result<TableDefinition, DiceError> {
+    use serde_json::value::RawValue;
+
+    #[derive(serde::Deserialize)]
+    struct JsonTable<'a> {
+        #[serde(borrow)]
+        dice: &'a RawValue,
+        #[serde(borrow)]
+        rows: Vec<&'a RawValue>,
+    }
+
+    // Raw values borrow from the input, so their position in the input gives their line
+    fn parse<'a>(input: &str, raw: &'a RawValue) -> (usize, &'a str) {
+        let offset = raw.get().as_ptr() as usize - input.as_ptr() as usize;
+        (line_of(input, offset), raw.get())
+    }
+    let json_error = |line: usize, error: &serde_json::Error| DiceError::TableFile {
+        file: label.to_string(),
+        line: line + error.line().saturating_sub(1),
+        reason: error.to_string(),
+    };
+
+    let table: JsonTable = serde_json::from_str(input).map_err(|error| json_error(1, &error))?;
+    let (dice_line, dice) = parse(input, table.dice);
+    let dice: String = serde_json::from_str(dice).map_err(|error| json_error(dice_line, &error))?;
+
+    let mut rows = Vec::with_capacity(table.rows.len());
+    for raw_row in table.rows {
+        let (line, raw_row) = parse(input, raw_row);
+        let row: FileRow =
+            serde_json::from_str(raw_row).map_err(|error| json_error(line, &error))?;
+        rows.push(Row {
+            line,
+            range: row.range.into_string(),
+            result: row.result,
+        });
+    }
+
+    Ok(TableDefinition {
+        dice_line,
+        dice,
+        rows,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[cfg(feature = "csv")]
+    use rand::SeedableRng;
+
+    #[cfg(feature = "csv")]
+    const SEED: u64 = 42;
+
+    fn assert_error_on_line(result: Result<Table, DiceError>, expected_line: usize) {
+        match result {
+            Err(DiceError::TableFile { line, .. }) => assert_eq!(line, expected_line),
+            other => panic!("Expected a table file error, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_ranges() {
+        assert_eq!(parse_range("01-15", 100), Some((1, 15)));
+        assert_eq!(parse_range("7", 12), Some((7, 7)));
+        assert_eq!(parse_range("96–00", 100), Some((96, 100)));
+        assert_eq!(parse_range("-4--2", 4), Some((-4, -2)));
+        assert_eq!(parse_range("00", 20), Some((0, 0)));
+        assert_eq!(parse_range("one", 20), None);
+    }
+
+    #[test]
+    fn parses_results() {
+        assert_eq!(parse_result(" @Gems "), TableResult::Table("Gems".into()));
+        assert_eq!(parse_result("Roll twice"), TableResult::RollAgain(2));
+        assert_eq!(parse_result("roll 3 times"), TableResult::RollAgain(3));
+        assert_eq!(
+            parse_result("A rope"),
+            TableResult::Text("A rope".to_string())
+        );
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn reads_csv_tables() {
+        let input = "d6,Encounter\n1-3,Orcs\n4-6,Goblins\n";
+        let table = parse_table("Encounters", input, TableFormat::Csv).expect("Valid table");
+        let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let result = table.roll_from_rng(rng).expect("Valid roll");
+        assert_eq!(result.texts(), vec!["Orcs"]);
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn csv_errors_report_lines() {
+        assert_error_on_line(
+            parse_table("Bad", "d6,Result\n1-3,A\nfour,B\n", TableFormat::Csv),
+            3,
+        );
+        assert_error_on_line(
+            parse_table("Bad", "d6,Result\n1-3,A\n3-6,B\n", TableFormat::Csv),
+            3,
+        );
+        assert_error_on_line(
+            parse_table("Bad", "d6,Result\n1-2,A\n4-6,B\n", TableFormat::Csv),
+            3,
+        );
+        assert_error_on_line(
+            parse_table("Bad", "2d6 + d4,Result\n1-2,A\n", TableFormat::Csv),
+            1,
+        );
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn reads_fudge_dice_tables() {
+        let input = "4dF,Weather\n-4--1,Rain\n0,Cloud\n1-4,Sun\n";
+        let table = parse_table("Weather", input, TableFormat::Csv).expect("Valid table");
+        assert_eq!(table.entries()[0].range, -4..=-1);
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn reads_toml_tables() {
+        let input = r#"
+dice = "d100"
+
+[[rows]]
+range = "01-50"
+result = "Copper"
+
+[[rows]]
+range = "51-00"
+result = "@Gems"
+"#;
+        let table = parse_table("Coins", input, TableFormat::Toml).expect("Valid table");
+        assert_eq!(table.entries()[1].range, 51..=100);
+        assert_eq!(table.entries()[1].result, TableResult::Table("Gems".into()));
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn toml_errors_report_lines() {
+        let input = "dice = \"d4\"\n\n[[rows]]\nrange = 1\nresult = \"A\"\n\n[[rows]]\nrange = \"3-4\"\nresult = \"B\"\n";
+        assert_error_on_line(parse_table("Bad", input, TableFormat::Toml), 7);
+        assert_error_on_line(parse_table("Bad", "dice = \n", TableFormat::Toml), 1);
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn reads_json_tables() {
+        let input = r#"{
+  "dice": "d4",
+  "rows": [
+    { "range": 1, "result": "A" },
+    { "range": "2-4", "result": "Roll twice" }
+  ]
+}"#;
+        let table = parse_table("Letters", input, TableFormat::Json).expect("Valid table");
+        assert_eq!(table.entries()[1].result, TableResult::RollAgain(2));
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn json_errors_report_lines() {
+        let input = r#"{
+  "dice": "d4",
+  "rows": [
+    { "range": 1, "result": "A" },
+    { "range": "1-4", "result": "B" }
+  ]
+}"#;
+        assert_error_on_line(parse_table("Bad", input, TableFormat::Json), 5);
+        let input = "{\n  \"dice\": \"d4\",\n  \"rows\": [\n    { \"range\": 1 }\n  ]\n}";
+        assert_error_on_line(parse_table("Bad", input, TableFormat::Json), 4);
+    }
+
+    #[cfg(all(feature = "csv", feature = "toml"))]
+    #[test]
+    fn loads_referenced_tables_from_other_files() {
+        let directory =
+            std::env::temp_dir().join(format!("dnd_dice_roller_tables_{}", std::process::id()));
+        fs::create_dir_all(&directory).expect("Can create directory");
+        fs::write(
+            directory.join("treasure.csv"),
+            "d2,Treasure\n1,@gems\n2,@treasure\n",
+        )
+        .expect("Can write file");
+        fs::write(
+            directory.join("gems.toml"),
+            "dice = \"d1\"\n[[rows]]\nrange = 1\nresult = \"Ruby\"\n",
+        )
+        .expect("Can write file");
+
+        let mut tables = Tables::new();
+        let loaded = tables.load_file(directory.join("treasure.csv"));
+        fs::remove_dir_all(&directory).expect("Can remove directory");
+        loaded.expect("Valid tables");
+
+        assert!(tables.get("gems").is_some());
+        let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let result = tables.roll_from_rng("treasure", rng).expect("Valid roll");
+        assert!(result.texts().iter().all(|text| *text == "Ruby"));
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn rejects_references_outside_the_directory() {
+        for reference in [
+            "@../loot",
+            "@/etc/loot",
+            "@sub/loot",
+            "@..\\loot",
+            "@C:loot",
+            "@",
+        ] {
+            let input = format!("d2,Treasure\n1,Gold\n2,{reference}\n");
+            assert_error_on_line(parse_table("Treasure", &input, TableFormat::Csv), 3);
+        }
+
+        let directory =
+            std::env::temp_dir().join(format!("dnd_dice_roller_escape_{}", std::process::id()));
+        let tables_directory = directory.join("tables");
+        fs::create_dir_all(&tables_directory).expect("Can create directory");
+        fs::write(directory.join("secret.csv"), "d1,Secret\n1,Hidden\n").expect("Can write file");
+        fs::write(tables_directory.join("loot.csv"), "d1,Loot\n1,@../secret\n")
+            .expect("Can write file");
+
+        let mut tables = Tables::new();
+        let loaded = tables.load_file(tables_directory.join("loot.csv"));
+        fs::remove_dir_all(&directory).expect("Can remove directory");
+        assert!(matches!(loaded, Err(DiceError::TableFile { line: 2, .. })));
+        assert!(tables.get("../secret").is_none());
+    }
+
+    #[cfg(feature = "csv")]
+    #[test]
+    fn missing_referenced_file_errors() {
+        let directory =
+            std::env::temp_dir().join(format!("dnd_dice_roller_missing_{}", std::process::id()));
+        fs::create_dir_all(&directory).expect("Can create directory");
+        fs::write(directory.join("loot.csv"), "d1,Loot\n1,@nowhere\n").expect("Can write file");
+
+        let mut tables = Tables::new();
+        let loaded = tables.load_file(directory.join("loot.csv"));
+        fs::remove_dir_all(&directory).expect("Can remove directory");
+        assert!(matches!(loaded, Err(DiceError::UnknownTable(name)) if name == "nowhere"));
+    }
+}
@@ -0,0 +1,77 @@
+use crate::{dice_result::DiceSetResults, roll::Roll as DiceRoll};
+
+use rand::SeedableRng;
+use serde::Serialize;
+use std::str::FromStr;
+use wasm_bindgen::prelude::*;
+
+/// Represents a parsed dice input string for use from JavaScript.
+#[wasm_bindgen]
+pub struct Roll {
+    roll: DiceRoll,
+}
+
+#[wasm_bindgen]
+impl Roll {
+    /// Parses a dice input string e.g. `2d20 + 1 a, 3d6`.
+    ///
+    /// # Errors
+    /// Errors with the parse error message if the dice input string is in the wrong format.
+    #[wasm_bindgen(js_name = fromStr)]
+    pub fn parse(input: &str) -> Result<Roll, JsError> {
+        let roll = DiceRoll::from_str(input).map_err(|error| JsError::new(&error.to_string()))?;
+        Ok(Roll { roll })
+    }
+
+    /// Rolls the dice, using the OS RNG or a `Pcg64Mcg` RNG created from the seed given.
+    /// The seed is a plain JavaScript number from 0 to 4294967295 e.g. `roll.roll(42)`, or `undefined` for the OS RNG.
+    /// Produces an array with one object per comma separated group, in the shape of `DiceSetResults`.
+    ///
+    /// # Errors
+    /// Errors if the results cannot be converted to JavaScript values.
+    pub fn roll(&self, seed: Option<u32>) -> Result<JsValue, JsError> {
+        to_js(&roll_results(&self.roll, seed))
+    }
+}
+
+/// Parses and rolls a dice input string in one step, using the OS RNG or a `Pcg64Mcg` RNG created from the seed given.
+/// The seed is a plain JavaScript number from 0 to 4294967295 e.g. `roll("3d6", 42)`, or `undefined` for the OS RNG.
+///
+/// # Errors
+/// Errors with the parse error message if the dice input string is in the wrong format.
+#[wasm_bindgen]
+pub fn roll(input: &str, seed: Option<u32>) -> Result<JsValue, JsError> {
+    Roll::parse(input)?.roll(seed)
+}
+
+pub(crate) fn roll_results(roll: &DiceRoll, seed: Option<u32>) -> Vec<DiceSetResults> {
+    match seed {
+        Some(seed) => roll.roll_from_rng(rand_pcg::Pcg64Mcg::seed_from_u64(u64::from(seed))),
+        None => roll.roll(),
+    }
+}
+
+fn to_js(results: &[DiceSetResults]) -> Result<JsValue, JsError> {
+    // Missing second rolls become null rather than undefined, matching the JSON form
+    let serializer = serde_wasm_bindgen::Serializer::json_compatible();
+    results
+        .serialize(&serializer)
+        .map_err(|error| JsError::new(&error.to_string()))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const SEED: u32 = 42;
+
+    #[test]
+    fn seeded_rolls_are_repeatable() {
+        let roll = DiceRoll::from_str("3d6 + 1, d20 a").expect("Valid roll");
+        let results = roll_results(&roll, Some(SEED));
+        assert_eq!(results[0].dice_results[0].first_roll, vec![2, 6, 5]);
+        assert_eq!(results[0].final_result, 14);
+        assert!(results[1].dice_results[0].second_roll.is_some());
+        assert_eq!(results, roll_results(&roll, Some(SEED)));
+    }
+}
@@ -0,0 +1,305 @@
+use rand::Rng;
+
+/// The sides of every dice in a Year Zero pool.
+const SIDES: u32 = 6;
+
+/// Represents the colour of a dice in a Year Zero pool.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DiceCategory {
+    /// Dice from an attribute. A 1 is a bane which damages the attribute.
+    Base,
+    /// Dice from a skill. A 1 has no effect.
+    Skill,
+    /// Dice from gear. A 1 is a bane which damages the gear.
+    Gear,
+}
+
+impl DiceCategory {
+    /// Whether a 1 on this category of dice is a bane.
+    #[must_use]
+    pub fn has_banes(self) -> bool {
+        self != DiceCategory::Skill
+    }
+}
+
+/// Represents a Year Zero Engine pool of base, skill and gear dice.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct YearZeroPool {
+    /// The number of base dice.
+    pub base: u32,
+    /// The number of skill dice.
+    pub skill: u32,
+    /// The number of gear dice.
+    pub gear: u32,
+}
+
+impl YearZeroPool {
+    /// Creates a new `YearZeroPool`.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::year_zero::YearZeroPool;
+    ///
+    /// // Strength 3, Melee 2 and a weapon with a gear bonus of 1
+    /// let pool = YearZeroPool::new(3, 2, 1);
+    /// ```
+    #[must_use]
+    pub fn new(base: u32, skill: u32, gear: u32) -> Self {
+        YearZeroPool { base, skill, gear }
+    }
+
+    /// Rolls every dice in the pool. Using underlying OS RNG for the dice roll.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::year_zero::YearZeroPool;
+    ///
+    /// let result = YearZeroPool::new(3, 2, 1).roll_pool();
+    /// assert_eq!(result.dice.len(), 6);
+    /// ```
+    #[must_use]
+    pub fn roll_pool(&self) -> YearZeroResult {
+        let mut rng = rand::thread_rng();
+        self.roll_pool_from_rng(&mut rng)
+    }
+
+    /// Rolls every dice in the pool, base dice first followed by skill and gear dice. Uses a source of RNG passed in. Useful for testing.
+    ///
+    /// # Examples
+    /// ```
+    /// use rand::SeedableRng;
+    /// use dnd_dice_roller::year_zero::YearZeroPool;
+    ///
+    /// let rng = rand_pcg::Pcg64Mcg::seed_from_u64(42);
+    /// let result = YearZeroPool::new(3, 0, 0).roll_pool_from_rng(rng);
+    /// assert_eq!(result.successes(), 1);
+    /// ```
+    pub fn roll_pool_from_rng<R: Rng + Sized>(&self, mut rng: R) -> YearZeroResult {
+        let categories = [
+            (DiceCategory::Base, self.base),
+            (DiceCategory::Skill, self.skill),
+            (DiceCategory::Gear, self.gear),
+        ];
+        let dice = categories
+            .iter()
+            .flat_map(|(category, count)| std::iter::repeat_n(*category, *count as usize))
+            .map(|category| YearZeroDie {
+                category,
+                face: rng.gen_range(1..=SIDES),
+            })
+            .collect();
+
+        YearZeroResult { dice, pushes: 0 }
+    }
+}
+
+/// Represents a single rolled dice in a Year Zero pool.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct YearZeroDie {
+    /// The colour of the dice.
+    pub category: DiceCategory,
+    /// The face rolled, from 1 to 6.
+    pub face: u32,
+}
+
+impl YearZeroDie {
+    /// Whether the dice is a success.
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        self.face == SIDES
+    }
+
+    /// Whether the dice is a bane.
+    #[must_use]
+    pub fn is_bane(&self) -> bool {
+        self.face == 1 && self.category.has_banes()
+    }
+
+    /// Whether the dice is rerolled when the roll is pushed. Successes and banes are locked in.
+    #[must_use]
+    pub fn can_push(&self) -> bool {
+        !self.is_success() && !self.is_bane()
+    }
+}
+
+/// Represents the result of rolling a `YearZeroPool`, and of any pushes since.
+#[derive(Clone, PartialEq, Debug)]
+pub struct YearZeroResult {
+    /// Every dice in the pool, base dice first followed by skill and gear dice.
+    pub dice: Vec<YearZeroDie>,
+    /// The number of times the roll has been pushed.
+    pub pushes: u32,
+}
+
+impl YearZeroResult {
+    /// The number of 6s rolled.
+    #[must_use]
+    pub fn successes(&self) -> usize {
+        self.dice.iter().filter(|die| die.is_success()).count()
+    }
+
+    /// The number of 1s rolled on base and gear dice.
+    #[must_use]
+    pub fn banes(&self) -> usize {
+        self.dice.iter().filter(|die| die.is_bane()).count()
+    }
+
+    /// The number of 1s rolled on a category of dice e.g. the damage to the gear from `DiceCategory::Gear`.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::year_zero::{DiceCategory, YearZeroDie, YearZeroResult};
+    ///
+    /// let result = YearZeroResult {
+    ///     dice: vec![
+    ///         YearZeroDie { category: DiceCategory::Base, face: 1 },
+    ///         YearZeroDie { category: DiceCategory::Skill, face: 1 },
+    ///         YearZeroDie { category: DiceCategory::Gear, face: 1 },
+    ///     ],
+    ///     pushes: 1,
+    /// };
+    /// assert_eq!(result.banes(), 2);
+    /// assert_eq!(result.banes_of(DiceCategory::Gear), 1);
+    /// assert_eq!(result.banes_of(DiceCategory::Skill), 0);
+    /// ```
+    #[must_use]
+    pub fn banes_of(&self, category: DiceCategory) -> usize {
+        self.dice
+            .iter()
+            .filter(|die| die.category == category && die.is_bane())
+            .count()
+    }
+
+    /// Whether at least one success was rolled.
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        self.successes() > 0
+    }
+
+    /// Pushes the roll, rerolling every dice which is not a success or a bane. Using underlying OS RNG for the dice roll.
+    ///
+    /// # Examples
+    /// ```
+    /// use dnd_dice_roller::year_zero::YearZeroPool;
+    ///
+    /// let result = YearZeroPool::new(3, 2, 1).roll_pool();
+    /// let pushed = result.push();
+    /// assert_eq!(pushed.pushes, 1);
+    /// ```
+    #[must_use]
+    pub fn push(&self) -> YearZeroResult {
+        let mut rng = rand::thread_rng();
+        self.push_from_rng(&mut rng)
+    }
+
+    /// Pushes the roll, rerolling every dice which is not a success or a bane in order. Uses a source of RNG passed in. Useful for testing.
+    ///
+    /// # Examples
+    /// ```
+    /// use rand::SeedableRng;
+    /// use dnd_dice_roller::year_zero::YearZeroPool;
+    ///
+    /// let mut rng = rand_pcg::Pcg64Mcg::seed_from_u64(42);
+    /// let result = YearZeroPool::new(3, 2, 1).roll_pool_from_rng(&mut rng);
+    /// let pushed = result.push_from_rng(&mut rng);
+    /// assert!(pushed.successes() >= result.successes());
+    /// ```
+    #[must_use]
+    pub fn push_from_rng<R: Rng + Sized>(&self, mut rng: R) -> YearZeroResult {
+        let dice = self
+            .dice
+            .iter()
+            .map(|die| {
+                if die.can_push() {
+                    YearZeroDie {
+                        category: die.category,
+                        face: rng.gen_range(1..=SIDES),
+                    }
+                } else {
+                    *die
+                }
+            })
+            .collect();
+
+        YearZeroResult {
+            dice,
+            pushes: self.pushes + 1,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use rand::SeedableRng;
+
+    const SEED: u64 = 42;
+
+    fn die(category: DiceCategory, face: u32) -> YearZeroDie {
+        YearZeroDie { category, face }
+    }
+
+    #[test]
+    fn produces_predictable_results() {
+        let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let result = YearZeroPool::new(1, 1, 1).roll_pool_from_rng(rng);
+        let expected = vec![
+            die(DiceCategory::Base, 2),
+            die(DiceCategory::Skill, 6),
+            die(DiceCategory::Gear, 5),
+        ];
+        assert_eq!(result.dice, expected);
+        assert_eq!(result.successes(), 1);
+        assert_eq!(result.banes(), 0);
+    }
+
+    #[test]
+    fn skill_ones_are_not_banes() {
+        let result = YearZeroResult {
+            dice: vec![
+                die(DiceCategory::Base, 1),
+                die(DiceCategory::Skill, 1),
+                die(DiceCategory::Gear, 6),
+            ],
+            pushes: 0,
+        };
+        assert_eq!(result.banes(), 1);
+        assert_eq!(result.banes_of(DiceCategory::Base), 1);
+        assert!(result.is_success());
+    }
+
+    #[test]
+    fn push_keeps_successes_and_banes() {
+        let rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let result = YearZeroResult {
+            dice: vec![
+                die(DiceCategory::Base, 6),
+                die(DiceCategory::Base, 1),
+                die(DiceCategory::Skill, 1),
+                die(DiceCategory::Gear, 3),
+            ],
+            pushes: 0,
+        };
+        let pushed = result.push_from_rng(rng);
+        assert_eq!(pushed.pushes, 1);
+        assert_eq!(pushed.dice[0], die(DiceCategory::Base, 6));
+        assert_eq!(pushed.dice[1], die(DiceCategory::Base, 1));
+        // The skill 1 and gear 3 are rerolled, using the first two rolls of the seed
+        assert_eq!(pushed.dice[2], die(DiceCategory::Skill, 2));
+        assert_eq!(pushed.dice[3], die(DiceCategory::Gear, 6));
+    }
+
+    #[test]
+    fn pushing_never_loses_successes_or_banes() {
+        let mut rng = rand_pcg::Pcg64Mcg::seed_from_u64(SEED);
+        let pool = YearZeroPool::new(4, 3, 2);
+        for _ in 0..50 {
+            let result = pool.roll_pool_from_rng(&mut rng);
+            let pushed = result.push_from_rng(&mut rng).push_from_rng(&mut rng);
+            assert_eq!(pushed.pushes, 2);
+            assert!(pushed.successes() >= result.successes());
+            assert!(pushed.banes() >= result.banes());
+        }
+    }
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "ffi")]
+
+use std::path::Path;
+use std::process::Command;
+
+const HEADER: &str = "dnd_dice_roller.h";
+
+#[test]
+fn committed_header_is_up_to_date() {
+    let generated = Path::new(env!("OUT_DIR")).join(HEADER);
+    let committed = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("include")
+        .join(HEADER);
+    assert_eq!(
+        std::fs::read_to_string(committed).expect("Committed header exists"),
+        std::fs::read_to_string(generated).expect("Generated header exists"),
+        "include/{} is out of date, copy it from the build output",
+        HEADER
+    );
+}
+
+#[test]
+#[cfg(unix)]
+fn rolls_dice_from_c() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+    let target_dir = manifest_dir.join("target").join("ffi");
+
+    // A separate target directory avoids waiting on the lock held by the `cargo test` running this
+    let status = Command::new(env!("CARGO"))
+        .current_dir(manifest_dir)
+        .args([
+            "rustc",
+            "--lib",
+            "--features",
+            "ffi",
+            "--crate-type",
+            "staticlib",
+        ])
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .status()
+        .expect("Cargo runs");
+    assert!(status.success(), "Building the static library failed");
+
+    let program = target_dir.join("roll");
+    let status = Command::new(std::env::var("CC").unwrap_or_else(|_| String::from("cc")))
+        .arg(manifest_dir.join("tests").join("ffi").join("roll.c"))
+        .arg("-I")
+        .arg(manifest_dir.join("include"))
+        .arg(target_dir.join("debug").join("libdnd_dice_roller.a"))
+        .args(["-lpthread", "-ldl", "-lm", "-o"])
+        .arg(&program)
+        .status()
+        .expect("C compiler runs");
+    assert!(status.success(), "Compiling tests/ffi/roll.c failed");
+
+    let output = Command::new(&program).output().expect("C program runs");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "ok\n");
+}
@@ -0,0 +1,78 @@
+#include <stdio.h>
+#include <string.h>
+
+#include "dnd_dice_roller.h"
+
+#define CHECK(condition)                                              \
+  do {                                                                \
+    if (!(condition)) {                                               \
+      fprintf(stderr, "%s:%d: check failed: %s\n", __FILE__, __LINE__, \
+              #condition);                                            \
+      return 1;                                                       \
+    }                                                                 \
+  } while (0)
+
+static int rolls_seeded_dice(void) {
+  DiceRoll *roll = NULL;
+  DiceResults *results = NULL;
+  size_t count = 0;
+  size_t length = 0;
+  int32_t total = 0;
+  uint32_t faces[3] = {0};
+
+  CHECK(dice_roll_parse("3d6 + 1, d20 a", &roll) == DICE_STATUS_OK);
+  CHECK(dice_roll_roll_seeded(roll, 42, &results) == DICE_STATUS_OK);
+
+  CHECK(dice_results_set_count(results, &count) == DICE_STATUS_OK);
+  CHECK(count == 2);
+  CHECK(dice_results_final_result(results, 0, &total) == DICE_STATUS_OK);
+  CHECK(total == 14);
+  CHECK(dice_results_dice_count(results, 0, &count) == DICE_STATUS_OK);
+  CHECK(count == 1);
+
+  CHECK(dice_results_first_roll(results, 0, 0, faces, 3, &length) ==
+        DICE_STATUS_OK);
+  CHECK(length == 3);
+  CHECK(faces[0] == 2 && faces[1] == 6 && faces[2] == 5);
+  CHECK(dice_results_second_roll(results, 0, 0, NULL, 0, &length) ==
+        DICE_STATUS_OK);
+  CHECK(length == 0);
+  CHECK(dice_results_second_roll(results, 1, 0, NULL, 0, &length) ==
+        DICE_STATUS_OK);
+  CHECK(length == 1);
+
+  dice_results_free(results);
+  dice_roll_free(roll);
+  return 0;
+}
+
+static int reports_errors(void) {
+  DiceRoll *roll = NULL;
+  DiceResults *results = NULL;
+  int32_t total = 0;
+
+  CHECK(dice_roll_parse("2d", &roll) == DICE_STATUS_PARSE_ERROR);
+  CHECK(roll == NULL);
+  CHECK(strncmp(dice_last_error_message(), "Error parsing input", 19) == 0);
+  CHECK(dice_roll_parse(NULL, &roll) == DICE_STATUS_NULL_POINTER);
+
+  CHECK(dice_roll_parse("d20", &roll) == DICE_STATUS_OK);
+  CHECK(dice_roll_roll(roll, &results) == DICE_STATUS_OK);
+  CHECK(dice_results_final_result(results, 1, &total) ==
+        DICE_STATUS_OUT_OF_RANGE);
+  CHECK(dice_results_final_result(results, 0, &total) == DICE_STATUS_OK);
+  CHECK(total >= 1 && total <= 20);
+
+  dice_results_free(results);
+  dice_roll_free(roll);
+  dice_roll_free(NULL);
+  return 0;
+}
+
+int main(void) {
+  if (rolls_seeded_dice() != 0 || reports_errors() != 0) {
+    return 1;
+  }
+  printf("ok\n");
+  return 0;
+}
@@ -0,0 +1,20 @@
+use std::path::Path;
+use std::process::Command;
+
+#[test]
+fn parses_dice_without_std() {
+    let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+
+    // A separate target directory avoids waiting on the lock held by the `cargo test` running this
+    let status = Command::new(env!("CARGO"))
+        .current_dir(manifest_dir)
+        .args(["build", "--no-default-features", "--example", "no_std"])
+        .arg("--target-dir")
+        .arg(manifest_dir.join("target").join("no_std"))
+        .status()
+        .expect("Cargo runs");
+    assert!(
+        status.success(),
+        "Building examples/no_std.rs without std failed"
+    );
+}
@@ -0,0 +1,51 @@
+#![cfg(all(feature = "wasm", target_arch = "wasm32"))]
+
+use dnd_dice_roller::wasm::{roll, Roll};
+use wasm_bindgen::JsValue;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+fn field(value: &JsValue, name: &str) -> JsValue {
+    js_sys::Reflect::get(value, &JsValue::from_str(name)).expect("Field exists")
+}
+
+#[wasm_bindgen_test]
+fn seeded_rolls_produce_json_shaped_results() {
+    let results = roll("3d6 + 1", Some(42)).expect("Valid roll");
+    let set = js_sys::Array::from(&results).get(0);
+    assert_eq!(field(&set, "final_result").as_f64(), Some(14.0));
+
+    let dice = js_sys::Array::from(&field(&set, "dice_results")).get(0);
+    let first_roll: Vec<f64> = js_sys::Array::from(&field(&dice, "first_roll"))
+        .iter()
+        .filter_map(|value| value.as_f64())
+        .collect();
+    assert_eq!(first_roll, vec![2.0, 6.0, 5.0]);
+    assert!(field(&dice, "second_roll").is_null());
+}
+
+#[wasm_bindgen_test]
+fn advantage_includes_both_rolls() {
+    let parsed = Roll::parse("d20 + 2 a").expect("Valid roll");
+    let results = parsed.roll(None).expect("Rolls");
+    let set = js_sys::Array::from(&results).get(0);
+    let dice = js_sys::Array::from(&field(&set, "dice_results")).get(0);
+    assert_eq!(
+        js_sys::Array::from(&field(&dice, "second_roll")).length(),
+        1
+    );
+}
+
+#[wasm_bindgen_test]
+fn seeds_are_plain_numbers() {
+    // Seeds are `u32` so JavaScript passes a number, rather than a `BigInt` as `u64` would need
+    let seeded = Roll::parse("3d6 + 1").expect("Valid roll");
+    let results = seeded.roll(Some(u32::MAX)).expect("Rolls");
+    let repeated = seeded.roll(Some(u32::MAX)).expect("Rolls");
+    let total = |results: &JsValue| field(&js_sys::Array::from(results).get(0), "final_result");
+    assert_eq!(total(&results).as_f64(), total(&repeated).as_f64());
+}
+
+#[wasm_bindgen_test]
+fn invalid_input_errors() {
+    assert!(Roll::parse("2d").is_err());
+}